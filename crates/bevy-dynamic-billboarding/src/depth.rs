@@ -0,0 +1,122 @@
+use bevy::{
+    pbr::render_graph::PBR_PIPELINE_HANDLE,
+    prelude::*,
+    render::{
+        pipeline::PipelineDescriptor,
+        shader::{Shader, ShaderSource, ShaderStage},
+        wireframe::WIREFRAME_PIPELINE_HANDLE,
+    },
+};
+
+/// Location of the varying that carries `1 + w` from the vertex to the fragment stage. Bevy's own
+/// shaders stop at location 3.
+const LOG_DEPTH_LOCATION: u32 = 7;
+
+/// Depth layout shared by the main pass and the first pass. Logarithmic depth spreads precision
+/// evenly over orders of magnitude, so a cockpit and a planet 1e8 units away both resolve.
+/// The depth is written per fragment: per-vertex log depth is interpolated linearly across the
+/// triangle, which pushes large triangles near the camera in front of their neighbours.
+#[derive(Debug, Clone, Copy)]
+pub struct DepthSettings {
+    pub logarithmic: bool,
+    /// Furthest view distance in render units, at least the far plane of every camera.
+    pub far: f32,
+}
+
+impl Default for DepthSettings {
+    fn default() -> Self {
+        Self {
+            logarithmic: false,
+            far: 1_000_000_000.0,
+        }
+    }
+}
+
+impl DepthSettings {
+    /// Adds the logarithmic depth to the end of `main` in a GLSL vertex shader if it is on.
+    pub fn vertex_shader(&self, source: &str) -> Shader {
+        let source = self.inject(
+            source,
+            "out",
+            "    v_LogDepth = 1.0 + gl_Position.w;\n    \
+             gl_Position.z = log2(max(1e-6, v_LogDepth)) / log2(1.0 + LOG_DEPTH_FAR) * gl_Position.w;\n",
+        );
+        Shader::from_glsl(ShaderStage::Vertex, &source)
+    }
+
+    /// Writes `gl_FragDepth` at the end of `main` in a GLSL fragment shader if logarithmic depth is
+    /// on. Pair it with a vertex shader from `DepthSettings::vertex_shader`.
+    pub fn fragment_shader(&self, source: &str) -> Shader {
+        let source = self.inject(
+            source,
+            "in",
+            "    gl_FragDepth = log2(max(1e-6, v_LogDepth)) / log2(1.0 + LOG_DEPTH_FAR);\n",
+        );
+        Shader::from_glsl(ShaderStage::Fragment, &source)
+    }
+
+    /// Declares `LOG_DEPTH_FAR` and `v_LogDepth` after the `#version` line and appends `body` to
+    /// `main`, which has to be the last function of the shader.
+    fn inject(&self, source: &str, qualifier: &str, body: &str) -> String {
+        if !self.logarithmic {
+            return source.to_string();
+        }
+        let (header, rest) = match source.find("#version").map(|start| {
+            let end = source[start..]
+                .find('\n')
+                .map_or(source.len(), |line| start + line + 1);
+            source.split_at(end)
+        }) {
+            Some(split) => split,
+            None => return source.to_string(),
+        };
+        let end_of_main = match rest.rfind('}') {
+            Some(end) => end,
+            None => return source.to_string(),
+        };
+        format!(
+            "{}#define LOG_DEPTH_FAR {:.1}\nlayout(location = {}) {} float v_LogDepth;\n{}{}{}",
+            header,
+            self.far,
+            LOG_DEPTH_LOCATION,
+            qualifier,
+            &rest[..end_of_main],
+            body,
+            &rest[end_of_main..]
+        )
+    }
+}
+
+/// Rewrites the shaders bevy registered for the PBR and wireframe pipelines, which both passes
+/// draw with, so only the depth code is ours and the rest follows the bevy version in use.
+pub(crate) fn apply_depth_settings(
+    depth: Res<DepthSettings>,
+    mut pipelines: ResMut<Assets<PipelineDescriptor>>,
+    mut shaders: ResMut<Assets<Shader>>,
+) {
+    if !depth.logarithmic {
+        return;
+    }
+    for handle in [PBR_PIPELINE_HANDLE, WIREFRAME_PIPELINE_HANDLE] {
+        let pipeline = match pipelines.get_mut(handle) {
+            Some(pipeline) => pipeline,
+            None => continue,
+        };
+        let stages = &mut pipeline.shader_stages;
+        if let Some(source) = glsl(&shaders, &stages.vertex) {
+            stages.vertex = shaders.add(depth.vertex_shader(&source));
+        }
+        if let Some(fragment) = stages.fragment.as_mut() {
+            if let Some(source) = glsl(&shaders, fragment) {
+                *fragment = shaders.add(depth.fragment_shader(&source));
+            }
+        }
+    }
+}
+
+fn glsl(shaders: &Assets<Shader>, handle: &Handle<Shader>) -> Option<String> {
+    match &shaders.get(handle)?.source {
+        ShaderSource::Glsl(source) => Some(source.clone()),
+        ShaderSource::Spirv(_) => None,
+    }
+}
@@ -35,8 +35,10 @@ use tags::{BillboardTag, FirstPass, FirstPassCube, MainPassCube};
 pub use texture_node::TextureNode;
 
 mod add_shader;
+mod depth;
 
 pub use add_shader::PIPELINE_HANDLE;
+pub use depth::DepthSettings;
 
 #[derive(Default)]
 pub struct DynamicBillboardingPlugin;
@@ -44,7 +46,9 @@ pub struct DynamicBillboardingPlugin;
 impl Plugin for DynamicBillboardingPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_event::<BillboardingTranslationEvent>()
-            .add_startup_system(setup_simple.system());
+            .init_resource::<DepthSettings>()
+            .add_startup_system(setup_simple.system())
+            .add_startup_system(depth::apply_depth_settings.system());
         // .add_system(secondary_startup.system());
         // .add_startup_system(setup_pipeline.system())
         // .add_startup_system(setup_simple.system());
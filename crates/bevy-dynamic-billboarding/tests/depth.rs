@@ -0,0 +1,57 @@
+use bevy::render::shader::{Shader, ShaderSource};
+use bevy_dynamic_billboarding::DepthSettings;
+
+const VERTEX: &str = include_str!("../../../assets/billboard.vert");
+const FRAGMENT: &str = include_str!("../../../assets/billboard.frag");
+
+const LOGARITHMIC: DepthSettings = DepthSettings {
+    logarithmic: true,
+    far: 1_000_000_000.0,
+};
+
+fn glsl(shader: &Shader) -> &str {
+    match &shader.source {
+        ShaderSource::Glsl(source) => source,
+        ShaderSource::Spirv(_) => panic!("expected a GLSL shader"),
+    }
+}
+
+#[test]
+fn linear_depth_leaves_shaders_untouched() {
+    let linear = DepthSettings::default();
+
+    assert_eq!(glsl(&linear.vertex_shader(VERTEX)), VERTEX);
+    assert_eq!(glsl(&linear.fragment_shader(FRAGMENT)), FRAGMENT);
+}
+
+#[test]
+fn depth_is_written_per_fragment() {
+    let vertex = LOGARITHMIC.vertex_shader(VERTEX);
+    let fragment = LOGARITHMIC.fragment_shader(FRAGMENT);
+    let (vertex, fragment) = (glsl(&vertex), glsl(&fragment));
+
+    let output = "layout(location = 7) out float v_LogDepth;";
+    let input = "layout(location = 7) in float v_LogDepth;";
+    assert!(vertex.contains(output));
+    assert!(fragment.contains(input));
+    assert!(fragment.find("#version").unwrap() < fragment.find(input).unwrap());
+
+    // Both writes land at the end of main, after the shader's own position and colour
+    let vertex_write = vertex.rfind("gl_Position.z = log2").unwrap();
+    assert!(vertex.rfind("gl_Position.xyz +=").unwrap() < vertex_write);
+    let fragment_write = fragment.rfind("gl_FragDepth = log2").unwrap();
+    assert!(fragment.rfind("o_Target = output_color;").unwrap() < fragment_write);
+    assert_eq!(
+        fragment[fragment_write..].trim_end().chars().last(),
+        Some('}')
+    );
+}
+
+#[test]
+fn injected_shaders_compile() {
+    LOGARITHMIC.vertex_shader(VERTEX).get_spirv(None).unwrap();
+    LOGARITHMIC
+        .fragment_shader(FRAGMENT)
+        .get_spirv(None)
+        .unwrap();
+}
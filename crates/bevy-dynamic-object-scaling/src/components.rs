@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_world_scale::WorldScale;
+
+/// Shrinks an entity once it is further than `threshold` from the active 3D camera, so far
+/// bodies don't swamp the view. The factor comes from `curve` and never drops below `min_scale`.
+#[derive(Debug, Clone, Copy)]
+pub struct DistanceScaling {
+    /// Distance in metres.
+    pub threshold: f64,
+    pub min_scale: f32,
+    pub curve: ScalingCurve,
+}
+
+impl Default for DistanceScaling {
+    fn default() -> Self {
+        Self {
+            threshold: 500.0,
+            min_scale: 0.0,
+            curve: ScalingCurve::Inverse,
+        }
+    }
+}
+
+impl DistanceScaling {
+    pub fn new(threshold: f64, curve: ScalingCurve) -> Self {
+        Self {
+            threshold,
+            curve,
+            ..Default::default()
+        }
+    }
+
+    /// Scale factor at a distance in render units. One within the threshold.
+    pub fn factor(&self, distance: f32, scale: &WorldScale) -> f32 {
+        let threshold = scale.length(self.threshold);
+        if distance <= threshold {
+            return 1.0;
+        }
+        self.curve.apply(distance / threshold).max(self.min_scale)
+    }
+}
+
+/// The scale an entity with `DistanceScaling` has while within its threshold, taken from its
+/// `Transform` the first time it is scaled. Change this rather than `Transform.scale` to resize it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuthoredScale(pub Vec3);
+
+#[derive(Debug, Clone, Copy)]
+pub enum ScalingCurve {
+    /// `threshold / distance`, which keeps the angular size it had at the threshold.
+    Inverse,
+    /// `1 / (1 + ln(distance / threshold))`, which shrinks far more slowly.
+    Logarithmic,
+    /// Gets the distance over the threshold, always more than one.
+    Custom(fn(f32) -> f32),
+}
+
+impl ScalingCurve {
+    pub fn apply(&self, ratio: f32) -> f32 {
+        match self {
+            ScalingCurve::Inverse => 1.0 / ratio,
+            ScalingCurve::Logarithmic => 1.0 / (1.0 + ratio.ln()),
+            ScalingCurve::Custom(curve) => curve(ratio),
+        }
+    }
+}
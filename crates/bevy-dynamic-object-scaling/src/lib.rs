@@ -1,35 +1,73 @@
-pub mod events;
-pub mod tags;
+pub mod components;
 
-use bevy::prelude::*;
-use events::ScalingTranslationEvent;
-use tags::ScalingObjectTag;
+use bevy::{
+    prelude::*,
+    render::{camera::ActiveCameras, render_graph::base::camera::CAMERA_3D},
+};
+use bevy_world_scale::WorldScale;
+use components::{AuthoredScale, DistanceScaling};
 
 #[derive(Default)]
 pub struct DynamicObjectScalingPlugin;
 
 impl Plugin for DynamicObjectScalingPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.add_event::<ScalingTranslationEvent>()
+        app.init_resource::<WorldScale>()
+            .init_resource::<ActiveCameras>()
             .add_system(change_scale_with_distance.system());
     }
 }
 
+/// Rescales entities whenever the camera or the entity has moved, or its `DistanceScaling` or
+/// `AuthoredScale` has changed, since the last run, whatever moved them.
+#[allow(clippy::type_complexity)]
 fn change_scale_with_distance(
-    mut translations: EventReader<ScalingTranslationEvent>,
-    mut query: Query<&mut Transform, With<ScalingObjectTag>>,
+    mut commands: Commands,
+    scale: Res<WorldScale>,
+    active_cameras: Res<ActiveCameras>,
+    cameras: Query<(&GlobalTransform, ChangeTrackers<GlobalTransform>)>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &GlobalTransform,
+        &DistanceScaling,
+        Option<&AuthoredScale>,
+        (
+            ChangeTrackers<GlobalTransform>,
+            ChangeTrackers<DistanceScaling>,
+            Option<ChangeTrackers<AuthoredScale>>,
+        ),
+    )>,
 ) {
-    for translation in translations.iter() {
-        for mut transform in query.iter_mut() {
-            let distance = (translation).distance(transform.translation);
-            if distance > 500.0 {
-                let scaling_factor = get_scaling_factor(distance);
-                transform.scale = Vec3::new(scaling_factor, scaling_factor, scaling_factor);
+    let (camera, camera_tracker) = match active_cameras
+        .get(CAMERA_3D)
+        .and_then(|active| active.entity)
+        .and_then(|camera| cameras.get(camera).ok())
+    {
+        Some(camera) => camera,
+        None => return,
+    };
+
+    for (entity, mut transform, global, scaling, authored, (moved, rescaled, resized)) in
+        query.iter_mut()
+    {
+        let resized = matches!(resized, Some(resized) if resized.is_changed());
+        if !camera_tracker.is_changed() && !moved.is_changed() && !rescaled.is_changed() && !resized
+        {
+            continue;
+        }
+        let authored = match authored {
+            Some(authored) => *authored,
+            None => {
+                let authored = AuthoredScale(transform.scale);
+                commands.entity(entity).insert(authored);
+                authored
             }
+        };
+        let distance = camera.translation.distance(global.translation);
+        let scaled = authored.0 * scaling.factor(distance, &scale);
+        if transform.scale != scaled {
+            transform.scale = scaled;
         }
     }
 }
-
-fn get_scaling_factor(distance: f32) -> f32 {
-    500.0 / distance
-}
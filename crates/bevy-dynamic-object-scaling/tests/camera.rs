@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy::render::{
+    camera::{active_cameras_system, ActiveCameras, Camera},
+    render_graph::base::camera::CAMERA_3D,
+};
+use bevy::transform::TransformPlugin;
+use bevy_dynamic_object_scaling::components::*;
+use bevy_dynamic_object_scaling::DynamicObjectScalingPlugin;
+
+fn app() -> (AppBuilder, Entity) {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(TransformPlugin)
+        .add_plugin(DynamicObjectScalingPlugin)
+        .add_system_to_stage(CoreStage::PostUpdate, active_cameras_system.system());
+    app.world_mut()
+        .get_resource_mut::<ActiveCameras>()
+        .unwrap()
+        .add(CAMERA_3D);
+    let camera = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Camera {
+                name: Some(CAMERA_3D.into()),
+                ..Default::default()
+            },
+            Transform::identity(),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    // Lets `active_cameras_system` pick the camera up by its name
+    app.app.update();
+    (app, camera)
+}
+
+fn move_camera(app: &mut AppBuilder, camera: Entity, translation: Vec3) {
+    app.world_mut()
+        .get_mut::<Transform>(camera)
+        .unwrap()
+        .translation = translation;
+    // Once for the transform to propagate and once for scaling to see it
+    app.app.update();
+    app.app.update();
+}
+
+fn scale(app: &mut AppBuilder, entity: Entity) -> Vec3 {
+    app.world_mut().get::<Transform>(entity).unwrap().scale
+}
+
+#[test]
+fn scaling_follows_the_camera_and_restores_the_authored_scale() {
+    let (mut app, camera) = app();
+    let authored = Vec3::new(0.3, 0.7, 1.1);
+    let body = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform {
+                translation: Vec3::new(200.0, 0.0, 0.0),
+                scale: authored,
+                ..Default::default()
+            },
+            GlobalTransform::identity(),
+            DistanceScaling::default(),
+        ))
+        .id();
+    app.app.update();
+    assert_eq!(scale(&mut app, body), authored);
+    assert_eq!(
+        app.world_mut().get::<AuthoredScale>(body),
+        Some(&AuthoredScale(authored))
+    );
+
+    move_camera(&mut app, camera, Vec3::new(-1_800.0, 0.0, 0.0));
+    assert_eq!(scale(&mut app, body), authored * 0.25);
+
+    move_camera(&mut app, camera, Vec3::new(100.0, 0.0, 0.0));
+    assert_eq!(scale(&mut app, body), authored);
+
+    app.world_mut().get_mut::<AuthoredScale>(body).unwrap().0 = Vec3::ONE;
+    app.app.update();
+    assert_eq!(scale(&mut app, body), Vec3::ONE);
+}
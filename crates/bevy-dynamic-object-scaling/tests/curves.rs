@@ -0,0 +1,40 @@
+use bevy_dynamic_object_scaling::components::*;
+use bevy_world_scale::WorldScale;
+
+const METRES: WorldScale = WorldScale(1.0);
+
+#[test]
+fn nothing_is_scaled_within_the_threshold() {
+    let scaling = DistanceScaling::default();
+    assert_eq!(scaling.factor(500.0, &METRES), 1.0);
+    assert_eq!(scaling.factor(10.0, &METRES), 1.0);
+}
+
+#[test]
+fn curves_shrink_past_the_threshold() {
+    let inverse = DistanceScaling::new(500.0, ScalingCurve::Inverse);
+    let logarithmic = DistanceScaling::new(500.0, ScalingCurve::Logarithmic);
+    let halving = DistanceScaling::new(500.0, ScalingCurve::Custom(|_| 0.5));
+
+    assert_eq!(inverse.factor(2_000.0, &METRES), 0.25);
+    assert!((logarithmic.factor(500.0 * std::f32::consts::E, &METRES) - 0.5).abs() < 1e-6);
+    assert_eq!(halving.factor(1.0e9, &METRES), 0.5);
+}
+
+#[test]
+fn threshold_is_converted_to_render_units() {
+    let scaling = DistanceScaling::new(2_000.0, ScalingCurve::Inverse);
+    let kilometres = WorldScale(0.001);
+
+    assert_eq!(scaling.factor(2.0, &kilometres), 1.0);
+    assert_eq!(scaling.factor(8.0, &kilometres), 0.25);
+}
+
+#[test]
+fn factor_is_clamped_to_the_minimum_scale() {
+    let scaling = DistanceScaling {
+        min_scale: 0.1,
+        ..Default::default()
+    };
+    assert_eq!(scaling.factor(1.0e9, &METRES), 0.1);
+}
@@ -0,0 +1,76 @@
+use bevy::math::DVec3;
+use bevy_inspector_egui::Inspectable;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// Index of a rebasing cell. Cells are centred on multiples of the cell size, so cell zero spans
+/// `-cell_size / 2..cell_size / 2` on every axis. i64 keeps indices exact at any distance.
+#[derive(Debug, Default, Inspectable, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GridCell {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl GridCell {
+    pub const ZERO: GridCell = GridCell { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The cell containing `translation`, along with the offset from that cell's centre.
+    pub fn from_translation(translation: DVec3, cell_size: f64) -> (Self, DVec3) {
+        let index = |value: f64| (value / cell_size).round();
+        let cell = DVec3::new(
+            index(translation.x),
+            index(translation.y),
+            index(translation.z),
+        );
+        (
+            Self::new(cell.x as i64, cell.y as i64, cell.z as i64),
+            translation - cell * cell_size,
+        )
+    }
+
+    /// Translation of this cell's centre relative to cell zero.
+    pub fn translation(&self, cell_size: f64) -> DVec3 {
+        DVec3::new(self.x as f64, self.y as f64, self.z as f64) * cell_size
+    }
+}
+
+impl Add for GridCell {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for GridCell {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for GridCell {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for GridCell {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for GridCell {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{FloatingOrigin, OriginRebasingSettings, SimulationBundle};
+use bevy::{math::DVec3, prelude::*};
+use bevy_world_scale::WorldScale;
+
+/// Draws a root entity further than the view distance at the view distance instead, along its
+/// true direction from the anchor and shrunk so it keeps the same angular size. Only its
+/// `GlobalTransform` and those of its children are compressed, its `Transform` stays the real
+/// render translation, so it can be moved and rebased like any other entity.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SceneCompression;
+
+#[derive(Bundle, Default)]
+pub struct CompressedSimulationBundle {
+    #[bundle]
+    simulation: SimulationBundle,
+    compression: SceneCompression,
+}
+
+impl CompressedSimulationBundle {
+    pub fn new(pos: Vec3, settings: &OriginRebasingSettings, scale: &WorldScale) -> Self {
+        Self {
+            simulation: SimulationBundle::new(pos, settings, scale),
+            compression: SceneCompression,
+        }
+    }
+}
+
+/// How much something `distance` away from the anchor is pulled in and shrunk.
+fn compression_factor(distance: f64, view_distance: f64) -> f64 {
+    if distance > view_distance {
+        view_distance / distance
+    } else {
+        1.0
+    }
+}
+
+/// Runs after transform propagation and recomputes the whole hierarchy from `Transform`, since
+/// propagation leaves unchanged entities with last frame's compressed `GlobalTransform`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn compress_far_objects(
+    settings: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    origin: Res<FloatingOrigin>,
+    compressed: Query<(Entity, &Transform), (With<SceneCompression>, Without<Parent>)>,
+    transforms: Query<(&Transform, Option<&Children>)>,
+    mut globals: Query<&mut GlobalTransform>,
+) {
+    let view_distance = scale.length(settings.view_distance) as f64;
+    // Without an anchor everything is compressed around the centre of the origin cell
+    let eye = origin
+        .anchor
+        .and_then(|anchor| globals.get_mut(anchor).ok())
+        .map_or(DVec3::ZERO, |anchor| anchor.translation.as_f64());
+
+    for (entity, transform) in compressed.iter() {
+        let distance = (transform.translation.as_f64() - eye).length();
+        let factor = compression_factor(distance, view_distance);
+        compress(
+            entity,
+            GlobalTransform::from(*transform),
+            eye,
+            factor,
+            &transforms,
+            &mut globals,
+        );
+    }
+}
+
+/// Scales `entity` and its descendants by `factor` around the eye, given its uncompressed
+/// `GlobalTransform`.
+fn compress(
+    entity: Entity,
+    global: GlobalTransform,
+    eye: DVec3,
+    factor: f64,
+    transforms: &Query<(&Transform, Option<&Children>)>,
+    globals: &mut Query<&mut GlobalTransform>,
+) {
+    if let Ok(mut compressed) = globals.get_mut(entity) {
+        *compressed = GlobalTransform {
+            translation: (eye + (global.translation.as_f64() - eye) * factor).as_f32(),
+            scale: global.scale * factor as f32,
+            ..global
+        };
+    }
+    if let Ok((_, Some(children))) = transforms.get(entity) {
+        for child in children.iter() {
+            if let Ok((transform, _)) = transforms.get(*child) {
+                let child_global = global.mul_transform(*transform);
+                compress(*child, child_global, eye, factor, transforms, globals);
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{SimulationCoordinates, SCENE_COMPRESSION_SYSTEM};
+use bevy::prelude::*;
+use bevy_inspector_egui::{egui, Context, Inspectable};
+use bevy_world_scale::WorldScale;
+use std::collections::BTreeMap;
+
+pub const PRECISION_DIAGNOSTICS_SYSTEM: &str = "precision_diagnostics";
+
+#[derive(Debug, Clone, Copy)]
+pub struct PrecisionDiagnosticsSettings {
+    /// Largest gap between neighbouring f32 translations, in metres, before an entity is
+    /// reported. Anything coarser shows up as jitter.
+    pub max_ulp: f64,
+}
+
+impl Default for PrecisionDiagnosticsSettings {
+    fn default() -> Self {
+        Self { max_ulp: 0.001 }
+    }
+}
+
+/// How precisely an entity's render `Transform` can place it, updated every frame.
+#[derive(Debug, Default, Inspectable, Clone, Copy, PartialEq)]
+pub struct TranslationPrecision {
+    /// Length of `Transform.translation`, in render units.
+    pub distance: f32,
+    /// Gap to the next f32 after `distance`, in render units.
+    pub ulp: f32,
+    pub within_budget: bool,
+}
+
+/// Entities with `SimulationCoordinates` counted by the ULP of their render translation, which is
+/// always a power of two. Keys are its exponent in render units.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PrecisionHistogram {
+    pub buckets: BTreeMap<i32, usize>,
+    pub over_budget: usize,
+    /// The scale the buckets were counted at, to label them in metres.
+    pub scale: WorldScale,
+}
+
+/// Shows the histogram as one bar per bucket, e.g. with `InspectorPlugin::<PrecisionHistogram>`.
+impl Inspectable for PrecisionHistogram {
+    type Attributes = ();
+
+    fn ui(&mut self, ui: &mut egui::Ui, _: Self::Attributes, _: &Context) -> bool {
+        let total = self.buckets.values().sum::<usize>().max(1);
+        ui.vertical(|ui| {
+            let width = ui.available_width();
+            for (exponent, count) in &self.buckets {
+                let metres = self.scale.to_metres(2f32.powi(*exponent));
+                ui.label(format!("{:.1e} m: {}", metres, count));
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(width, 8.0), egui::Sense::hover());
+                let bar = egui::Rect::from_min_size(
+                    rect.min,
+                    egui::vec2(width * *count as f32 / total as f32, rect.height()),
+                );
+                ui.painter()
+                    .rect_filled(bar, 0.0, ui.visuals().selection.bg_fill);
+            }
+            ui.label(format!("Over budget: {}", self.over_budget));
+        });
+        false
+    }
+}
+
+/// Adds a `TranslationPrecision` to every entity with `SimulationCoordinates`, fills the
+/// `PrecisionHistogram` and logs a warning whenever an entity leaves the precision budget.
+#[derive(Default)]
+pub struct PrecisionDiagnosticsPlugin {
+    pub settings: PrecisionDiagnosticsSettings,
+}
+
+impl PrecisionDiagnosticsPlugin {
+    pub fn new(settings: PrecisionDiagnosticsSettings) -> Self {
+        Self { settings }
+    }
+}
+
+impl Plugin for PrecisionDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(self.settings)
+            .init_resource::<PrecisionHistogram>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                diagnose_precision
+                    .system()
+                    .label(PRECISION_DIAGNOSTICS_SYSTEM)
+                    .after(SCENE_COMPRESSION_SYSTEM),
+            );
+    }
+}
+
+/// Distance from `value` to the next f32 away from zero.
+pub fn ulp(value: f32) -> f32 {
+    let value = value.abs();
+    f32::from_bits(value.to_bits() + 1) - value
+}
+
+#[allow(clippy::type_complexity)]
+fn diagnose_precision(
+    mut commands: Commands,
+    settings: Res<PrecisionDiagnosticsSettings>,
+    scale: Res<WorldScale>,
+    mut histogram: ResMut<PrecisionHistogram>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&Name>,
+            Option<&mut TranslationPrecision>,
+        ),
+        With<SimulationCoordinates>,
+    >,
+) {
+    let budget = scale.length(settings.max_ulp);
+    histogram.buckets.clear();
+    histogram.over_budget = 0;
+    histogram.scale = *scale;
+
+    for (entity, transform, name, precision) in query.iter_mut() {
+        let distance = transform.translation.length();
+        let ulp = ulp(distance);
+        let current = TranslationPrecision {
+            distance,
+            ulp,
+            within_budget: ulp <= budget,
+        };
+        *histogram.buckets.entry(ulp.log2() as i32).or_default() += 1;
+        if !current.within_budget {
+            histogram.over_budget += 1;
+        }
+
+        let was_over_budget =
+            matches!(precision.as_deref(), Some(previous) if !previous.within_budget);
+        if !was_over_budget && !current.within_budget {
+            warn!(
+                "{:?} ({}) is {} m from the render origin, where f32 translations are {} m apart",
+                entity,
+                name.map_or("unnamed", |name| name.as_str()),
+                scale.to_metres(distance),
+                scale.to_metres(ulp)
+            );
+        }
+        match precision {
+            Some(mut precision) if *precision != current => *precision = current,
+            Some(_) => {}
+            None => {
+                commands.entity(entity).insert(current);
+            }
+        }
+    }
+}
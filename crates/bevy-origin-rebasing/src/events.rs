@@ -1,15 +1,21 @@
+use crate::cell::GridCell;
 use bevy::prelude::*;
 use std::ops::Deref;
 
+/// Sent once per rebase. Derefs to the translation that was added to every rebased entity.
 #[derive(Debug, Default)]
 pub struct OrginRebasingEvent {
+    pub old_cell: GridCell,
+    pub new_cell: GridCell,
     pub translation: Vec3,
 }
 
 impl OrginRebasingEvent {
-    pub fn new(other: &Vec3) -> Self {
+    pub fn new(old_cell: GridCell, new_cell: GridCell, translation: &Vec3) -> Self {
         Self {
-            translation: *other,
+            old_cell,
+            new_cell,
+            translation: *translation,
         }
     }
 }
@@ -21,3 +27,43 @@ impl Deref for OrginRebasingEvent {
         &self.translation
     }
 }
+
+/// A cell came within the streaming radius of the anchor.
+#[derive(Debug, Default)]
+pub struct CellEnteredEvent {
+    pub cell: GridCell,
+}
+
+impl CellEnteredEvent {
+    pub fn new(cell: GridCell) -> Self {
+        Self { cell }
+    }
+}
+
+impl Deref for CellEnteredEvent {
+    type Target = GridCell;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cell
+    }
+}
+
+/// A cell left the streaming radius of the anchor and its streamed entities were stored.
+#[derive(Debug, Default)]
+pub struct CellExitedEvent {
+    pub cell: GridCell,
+}
+
+impl CellExitedEvent {
+    pub fn new(cell: GridCell) -> Self {
+        Self { cell }
+    }
+}
+
+impl Deref for CellExitedEvent {
+    type Target = GridCell;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cell
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{OriginRebasingSettings, SimulationCoordinates};
+use bevy::math::DVec3;
+use bevy_inspector_egui::{egui, Context, Inspectable};
+use bevy_world_scale::{WorldScale, METERS_PER_ASTRONOMICAL_UNIT, METERS_PER_KILOMETER};
+
+/// Shows the cell, the offset within it and the absolute position in km and AU. Editing the
+/// kilometres teleports the entity, see `SimulationCoordinates::teleport`.
+impl Inspectable for SimulationCoordinates {
+    type Attributes = ();
+
+    fn ui(&mut self, ui: &mut egui::Ui, _: Self::Attributes, context: &Context) -> bool {
+        // SAFETY: `self` is a component borrowed mutably out of this same world, so the world must
+        // not reach into component storage here. Only the `WorldScale` and `OriginRebasingSettings`
+        // resources are read, which are disjoint from `self`, and no reference outlives this match.
+        let (scale, settings) = match unsafe { context.world() } {
+            Some(world) => (
+                world
+                    .get_resource::<WorldScale>()
+                    .copied()
+                    .unwrap_or_default(),
+                world
+                    .get_resource::<OriginRebasingSettings>()
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+            None => Default::default(),
+        };
+        let cell_size = settings.render_cell_size(&scale);
+        let render_units_per_kilometre = scale.0 * METERS_PER_KILOMETER;
+
+        let mut changed = false;
+        ui.vertical(|ui| {
+            let cell = self.cell();
+            let local = self.local_translation();
+            ui.label(format!("Cell: {}, {}, {}", cell.x, cell.y, cell.z));
+            ui.label(format!(
+                "Offset: {:.3}, {:.3}, {:.3}",
+                local.x, local.y, local.z
+            ));
+
+            let mut kilometres: [f64; 3] = (self
+                .pending_teleport()
+                .unwrap_or_else(|| self.translation(cell_size))
+                / render_units_per_kilometre)
+                .into();
+            ui.horizontal(|ui| {
+                ui.label("km");
+                for value in kilometres.iter_mut() {
+                    changed |= ui.add(egui::DragValue::new(value)).changed();
+                }
+            });
+            let kilometres = DVec3::from(kilometres);
+            let au = kilometres * METERS_PER_KILOMETER / METERS_PER_ASTRONOMICAL_UNIT;
+            ui.label(format!("AU: {:.6}, {:.6}, {:.6}", au.x, au.y, au.z));
+
+            if changed {
+                self.teleport(kilometres * render_units_per_kilometre);
+            }
+        });
+        changed
+    }
+}
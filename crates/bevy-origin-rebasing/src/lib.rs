@@ -1,48 +1,94 @@
-use std::cmp::{self, min};
-
-use bevy::{math::DVec3, prelude::*};
+use bevy::{math::DVec3, prelude::*, transform::TransformSystem};
 use bevy_inspector_egui::Inspectable;
-use bevy_prototype_debug_lines::{DebugLines, DebugLinesPlugin, Line};
-use events::OrginRebasingEvent;
+use bevy_prototype_debug_lines::{DebugLines, DebugLinesPlugin};
+use bevy_world_scale::WorldScale;
+
+pub use cell::GridCell;
+pub use compression::{CompressedSimulationBundle, SceneCompression};
+pub use diagnostics::{
+    ulp, PrecisionDiagnosticsPlugin, PrecisionDiagnosticsSettings, PrecisionHistogram,
+    TranslationPrecision, PRECISION_DIAGNOSTICS_SYSTEM,
+};
+pub use events::{CellEnteredEvent, CellExitedEvent, OrginRebasingEvent};
+pub use rebaseable::{Rebaseable, RebaseableAppExt, REBASE_STATE_SYSTEM};
+pub use settings::OriginRebasingSettings;
+pub use store::{CellStorage, CellStore, FileStore, MemoryStore, StreamedEntity};
+pub use streaming::{CellStreamingPlugin, CellStreamingSettings, Streamed, STREAM_CELLS_SYSTEM};
+pub use views::{ViewInstance, ViewOrigin};
 
+mod cell;
+mod compression;
+mod diagnostics;
 mod events;
+mod inspector;
+mod rebaseable;
+mod settings;
+mod store;
+mod streaming;
+mod views;
 
-const MAX_BOUND: f32 = 1_000.0;
-const MAX_VIEW: f32 = 10.0 * MAX_BOUND;
+pub const TELEPORT_SYSTEM: &str = "teleport";
+pub const SYNC_SIMULATION_COORDINATES_SYSTEM: &str = "sync_simulation_coordinates";
+pub const VIEW_ORIGINS_SYSTEM: &str = "view_origins";
+pub const VIEW_INSTANCES_SYSTEM: &str = "view_instances";
+pub const SCENE_COMPRESSION_SYSTEM: &str = "scene_compression";
 
-#[derive(Default, Inspectable, Clone, Copy)]
+/// Position split into an exact grid cell and an f32 offset from the cell's centre,
+/// both in render units.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct SimulationCoordinates {
+    cell: GridCell,
     local_translation: Vec3,
-    solar_coordinates: Vec3,
-    world_coordinates: Vec3,
+    teleport: Option<DVec3>,
 }
 
 impl SimulationCoordinates {
-    pub fn from(pos: Vec3) -> Self {
+    pub fn new(cell: GridCell, local_translation: Vec3) -> Self {
         Self {
-            local_translation: Vec3::new(pos.x % MAX_BOUND, pos.y % MAX_BOUND, pos.z % MAX_BOUND),
-            solar_coordinates: Vec3::new(0.0, 0.0, (pos.z / (2.0 * MAX_BOUND)).ceil()),
-            world_coordinates: pos,
+            cell,
+            local_translation,
+            teleport: None,
         }
     }
 
-    pub fn get_relative_render_position(&self, s: SimulationCoordinates) -> Vec3 {
-        let result = (self.solar_coordinates * MAX_BOUND + self.local_translation)
-            - (s.solar_coordinates * MAX_BOUND + s.local_translation);
-        println!(
-            "{},{} - {},{} => {}",
-            self.solar_coordinates,
-            self.local_translation,
-            s.solar_coordinates,
-            s.local_translation,
-            result
-        );
-        result
-        // Self {
-        //     local_translation: Vec3::new(pos.x % MAX_BOUND, pos.y % MAX_BOUND, pos.z % MAX_BOUND),
-        //     solar_coordinates: Vec3::new(0.0, 0.0, (pos.z / (2.0 * MAX_BOUND)).ceil()),
-        //     world_coordinates: pos,
-        // }
+    /// Splits an absolute translation, relative to the centre of cell zero, into cell and offset.
+    pub fn from_translation(translation: DVec3, cell_size: f64) -> Self {
+        let (cell, offset) = GridCell::from_translation(translation, cell_size);
+        Self::new(cell, offset.as_f32())
+    }
+
+    pub fn cell(&self) -> GridCell {
+        self.cell
+    }
+
+    pub fn local_translation(&self) -> Vec3 {
+        self.local_translation
+    }
+
+    /// Absolute translation relative to the centre of cell zero.
+    pub fn translation(&self, cell_size: f64) -> DVec3 {
+        self.cell.translation(cell_size) + self.local_translation.as_f64()
+    }
+
+    /// The shift to apply to render translations when the origin moves from this cell to `to`'s cell.
+    pub fn cell_shift(&self, to: &SimulationCoordinates, cell_size: f64) -> Vec3 {
+        (self.cell - to.cell).translation(cell_size).as_f32()
+    }
+
+    /// Where this position renders when `origin`'s cell is the render origin.
+    pub fn relative_translation(&self, origin: &SimulationCoordinates, cell_size: f64) -> Vec3 {
+        self.cell_shift(origin, cell_size) + self.local_translation
+    }
+
+    /// Moves a root entity to an absolute translation, relative to the centre of cell zero,
+    /// before the next rebase. Teleporting the anchor rebases the world onto its new cell.
+    /// Children move with their parent, a teleport of one is dropped with a warning.
+    pub fn teleport(&mut self, translation: DVec3) {
+        self.teleport = Some(translation);
+    }
+
+    pub fn pending_teleport(&self) -> Option<DVec3> {
+        self.teleport
     }
 }
 
@@ -53,43 +99,43 @@ pub struct SimulationBundle {
 }
 
 impl SimulationBundle {
-    pub fn new(pos: Vec3) -> Self {
+    pub fn new(pos: Vec3, settings: &OriginRebasingSettings, scale: &WorldScale) -> Self {
         Self {
             transform: Transform::from_translation(pos),
-            simulation_coordinates: SimulationCoordinates::from(pos),
-        }
-    }
-    pub fn new_scaled(pos: Vec3) -> Self {
-        let scaling_factor = get_scaling_factor(pos.length());
-        // println!("sf -> 500.0 / {} = {}", pos.length(), scaling_factor);
-        let render_pos = Vec3::new(get_bound(pos.x), get_bound(pos.y), get_bound(pos.z));
-        Self {
-            transform: Transform::from_matrix(Mat4::from_scale_rotation_translation(
-                Vec3::splat(scaling_factor),
-                Quat::IDENTITY,
-                render_pos,
-            )),
-            simulation_coordinates: SimulationCoordinates::from(pos),
+            simulation_coordinates: SimulationCoordinates::from_translation(
+                pos.as_f64(),
+                settings.render_cell_size(scale),
+            ),
         }
     }
 }
 
-fn get_scaling_factor(distance: f32) -> f32 {
-    MAX_VIEW / distance
+#[derive(Default)]
+pub struct OriginRebasingPlugin {
+    pub settings: OriginRebasingSettings,
 }
 
-fn get_bound(x: f32) -> f32 {
-    if x < 0.0 {
-        x.max(-MAX_VIEW)
-    } else {
-        x.min(MAX_VIEW)
+impl OriginRebasingPlugin {
+    pub fn new(settings: OriginRebasingSettings) -> Self {
+        Self { settings }
     }
 }
 
-#[derive(Default)]
-pub struct OriginRebasingPlugin;
+/// Marks the entity the render origin follows, e.g. the player, a ship's camera or a spectator.
+/// Needs `SimulationCoordinates`. If several entities are anchors the most recently added one
+/// is used, and with none the origin simply stays where it is.
+pub struct FloatingOriginAnchor;
 
-pub struct PlayerTag;
+/// The cell the render origin is currently centred on and the anchor it follows.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FloatingOrigin {
+    pub cell: GridCell,
+    pub anchor: Option<Entity>,
+}
+
+/// Shifts this entity's `Transform` on every rebase even though it has no `SimulationCoordinates`
+/// or has a parent, e.g. a root light. Children of rebased entities follow their parent already.
+pub struct RebaseRoot;
 
 pub struct NonPlayerTag;
 
@@ -98,200 +144,243 @@ pub struct BillieTag;
 impl Plugin for OriginRebasingPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_event::<OrginRebasingEvent>()
-            .add_plugin(DebugLinesPlugin)
-            .insert_resource(DebugLines {
-                depth_test: true,
-                ..Default::default()
-            })
-            .add_startup_system(setup.system())
-            .add_system(sync_simulation_coordinates.system())
-            .add_system(rebase_origin.system());
+            .insert_resource(self.settings)
+            .init_resource::<WorldScale>()
+            .init_resource::<FloatingOrigin>()
+            .add_system(apply_teleports.system().label(TELEPORT_SYSTEM))
+            .add_system(
+                sync_simulation_coordinates
+                    .system()
+                    .label(SYNC_SIMULATION_COORDINATES_SYSTEM)
+                    .after(TELEPORT_SYSTEM),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                views::update_view_origins
+                    .system()
+                    .label(VIEW_ORIGINS_SYSTEM)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                views::place_view_instances
+                    .system()
+                    .label(VIEW_INSTANCES_SYSTEM)
+                    .after(TransformSystem::TransformPropagate)
+                    .before(SCENE_COMPRESSION_SYSTEM),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                compression::compress_far_objects
+                    .system()
+                    .label(SCENE_COMPRESSION_SYSTEM)
+                    .after(TransformSystem::TransformPropagate),
+            );
+
+        if self.settings.draw_debug_cell {
+            app.add_plugin(DebugLinesPlugin)
+                .insert_resource(DebugLines {
+                    depth_test: true,
+                    ..Default::default()
+                })
+                .insert_resource(DebugCell { centre: Vec3::ZERO })
+                .register_rebaseable_resource::<DebugCell>()
+                .add_system(draw_debug_cell.system().after(REBASE_STATE_SYSTEM));
+        }
     }
 }
 
-fn sync_simulation_coordinates(
+/// Root entities with `SimulationCoordinates` and flagged roots, the ones shifted on a rebase.
+pub type Rebased = Or<(
+    (With<SimulationCoordinates>, Without<Parent>),
+    With<RebaseRoot>,
+)>;
+
+/// Newly added anchors that have what `sync_simulation_coordinates` needs to place them.
+type AddedAnchor = (
+    Added<FloatingOriginAnchor>,
+    With<SimulationCoordinates>,
+    With<Transform>,
+    With<GlobalTransform>,
+);
+
+type InvalidAnchor = (
+    Added<FloatingOriginAnchor>,
+    Or<(
+        Without<SimulationCoordinates>,
+        Without<Transform>,
+        Without<GlobalTransform>,
+    )>,
+);
+
+#[allow(clippy::type_complexity)]
+fn apply_teleports(
+    settings: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    mut origin: ResMut<FloatingOrigin>,
     mut rebase_events: EventWriter<OrginRebasingEvent>,
     mut q: QuerySet<(
-        Query<(&Transform, &mut SimulationCoordinates), With<PlayerTag>>,
-        Query<(&mut Transform, &SimulationCoordinates), With<SimulationCoordinates>>,
-        Query<&mut SimulationCoordinates, With<NonPlayerTag>>,
+        Query<(Entity, &mut SimulationCoordinates, &mut Transform), Without<Parent>>,
+        Query<&mut Transform, Rebased>,
+        Query<(Entity, &mut SimulationCoordinates), With<Parent>>,
     )>,
 ) {
-    let mut shift = Vec3::ZERO;
-
-    let (transform, mut simulation_transform) = q
-        .q0_mut()
-        .single_mut()
-        .expect("There should always be exactly one player in the game!");
-
-    simulation_transform.local_translation = transform.translation;
-    if transform.translation.x < -MAX_BOUND {
-        simulation_transform.local_translation.x += 2.0 * MAX_BOUND;
-        simulation_transform.solar_coordinates.x -= 1.0;
-        shift = 2.0 * Vec3::X * MAX_BOUND;
-        rebase_events.send(OrginRebasingEvent::new(&shift));
-    } else if transform.translation.x > MAX_BOUND {
-        simulation_transform.local_translation.x -= 2.0 * MAX_BOUND;
-        simulation_transform.solar_coordinates.x += 1.0;
-        shift = -2.0 * Vec3::X * MAX_BOUND;
-        rebase_events.send(OrginRebasingEvent::new(&shift));
-    }
-    if transform.translation.y < -MAX_BOUND {
-        simulation_transform.local_translation.y += 2.0 * MAX_BOUND;
-        simulation_transform.solar_coordinates.y -= 1.0;
-        shift = 2.0 * Vec3::Y * MAX_BOUND;
-        rebase_events.send(OrginRebasingEvent::new(&shift));
-    } else if transform.translation.y > MAX_BOUND {
-        simulation_transform.local_translation.y -= 2.0 * MAX_BOUND;
-        simulation_transform.solar_coordinates.y += 1.0;
-        shift = -2.0 * Vec3::Y * MAX_BOUND;
-        rebase_events.send(OrginRebasingEvent::new(&shift));
+    for (entity, mut coordinates) in q.q2_mut().iter_mut() {
+        if coordinates.teleport.is_some() {
+            coordinates.teleport = None;
+            warn!(
+                "Dropping teleport of {:?}, only root entities can be teleported",
+                entity
+            );
+        }
     }
-    if transform.translation.z < -MAX_BOUND {
-        simulation_transform.local_translation.z += 2.0 * MAX_BOUND;
-        simulation_transform.solar_coordinates.z -= 1.0;
-        shift = 2.0 * Vec3::Z * MAX_BOUND;
-        rebase_events.send(OrginRebasingEvent::new(&shift));
-    } else if transform.translation.z > MAX_BOUND {
-        simulation_transform.local_translation.z -= 2.0 * MAX_BOUND;
-        simulation_transform.solar_coordinates.z += 1.0;
-        shift = -2.0 * Vec3::Z * MAX_BOUND;
-        rebase_events.send(OrginRebasingEvent::new(&shift));
+
+    let cell_size = settings.render_cell_size(&scale);
+    let mut teleports = Vec::new();
+    for (entity, mut coordinates, _) in q.q0_mut().iter_mut() {
+        if coordinates.teleport.is_none() {
+            continue;
+        }
+        if let Some(translation) = coordinates.teleport.take() {
+            teleports.push((
+                entity,
+                SimulationCoordinates::from_translation(translation, cell_size),
+            ));
+        }
     }
 
-    let a = simulation_transform.solar_coordinates.clone();
-    let b = simulation_transform.clone();
-
-    if shift != Vec3::ZERO {
-        for (mut transform, npc_simulation_coordinates) in q.q1_mut().iter_mut() {
-            // let distance = npc_simulation_coordinates.solar_coordinates.distance(a) * MAX_BOUND;
-            // if distance < MAX_VIEW {
-            transform.translation += shift;
-            // }
-            // else {
-            //     let scaling_factor = get_scaling_factor(distance).min(1.0);
-            //     let relative_render_position = transform.translation - npc_simulation_coordinates.get_relative_render_position(b);
-            //     transform.translation += relative_render_position * scaling_factor;
-            //     transform.scale = Vec3::splat(scaling_factor);
-            // }
+    // The anchor goes first, everything else is then placed relative to its new cell
+    teleports.sort_by_key(|(entity, _)| Some(*entity) != origin.anchor);
+    for (entity, target) in teleports {
+        if Some(entity) == origin.anchor && target.cell != origin.cell {
+            let shift =
+                SimulationCoordinates::new(origin.cell, Vec3::ZERO).cell_shift(&target, cell_size);
+            let old_cell = origin.cell;
+            origin.cell = target.cell;
+            rebase_events.send(OrginRebasingEvent::new(old_cell, origin.cell, &shift));
+            for mut transform in q.q1_mut().iter_mut() {
+                transform.translation += shift;
+            }
+        }
+        let origin_coordinates = SimulationCoordinates::new(origin.cell, Vec3::ZERO);
+        if let Ok((_, mut coordinates, mut transform)) = q.q0_mut().get_mut(entity) {
+            *coordinates = target;
+            transform.translation = target.relative_translation(&origin_coordinates, cell_size);
         }
     }
 }
 
-fn rebase_origin(
-    mut events: EventReader<OrginRebasingEvent>,
-    mut query: Query<&mut Transform, With<NonPlayerTag>>,
+#[allow(clippy::type_complexity)]
+fn sync_simulation_coordinates(
+    settings: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    mut origin: ResMut<FloatingOrigin>,
+    mut rebase_events: EventWriter<OrginRebasingEvent>,
+    added_anchors: Query<Entity, AddedAnchor>,
+    invalid_anchors: Query<Entity, InvalidAnchor>,
+    mut q: QuerySet<(
+        Query<
+            (
+                Entity,
+                &Transform,
+                &GlobalTransform,
+                Option<&Parent>,
+                &mut SimulationCoordinates,
+            ),
+            With<FloatingOriginAnchor>,
+        >,
+        Query<&mut Transform, Rebased>,
+    )>,
 ) {
-    // for event in events.iter() {
-    //     for mut transform in query.iter_mut() {
-    //         println!("transform -> {}", event.translation);
-    //         transform.translation += event.translation;
-    //         // let distance = (translation).distance(transform.translation);
-    //         // if distance > 500.0 {
-    //         //     let scaling_factor = get_scaling_factor(distance);
-    //         //     transform.scale = Vec3::new(scaling_factor, scaling_factor, scaling_factor);
-    //         // }
-    //     }
-    // }
+    for anchor in invalid_anchors.iter() {
+        warn!(
+            "Ignoring FloatingOriginAnchor on {:?}, it needs a Transform, GlobalTransform and SimulationCoordinates",
+            anchor
+        );
+    }
+    let current = origin
+        .anchor
+        .filter(|anchor| q.q0_mut().get_mut(*anchor).is_ok());
+    let anchor = added_anchors
+        .iter()
+        .last()
+        .or(current)
+        .or_else(|| q.q0_mut().iter_mut().next().map(|(entity, ..)| entity));
+    origin.anchor = anchor;
+    let anchor = match anchor {
+        Some(anchor) => anchor,
+        None => return,
+    };
+
+    // The anchor is placed by its render translation within the current origin cell, so switching
+    // to another anchor moves nothing until the new one crosses a cell boundary itself.
+    // Child anchors only have last frame's GlobalTransform, which delays the rebase by a frame.
+    let (_, transform, global_transform, parent, mut coordinates) = match q.q0_mut().get_mut(anchor)
+    {
+        Ok(anchor) => anchor,
+        Err(_) => return,
+    };
+    let translation = match parent {
+        Some(_) => global_transform.translation,
+        None => transform.translation,
+    };
+
+    let delta = settings.cells_crossed(translation, &scale);
+    // Only the position, a teleport requested since `apply_teleports` ran stays pending
+    coordinates.cell = origin.cell;
+    if delta == GridCell::ZERO {
+        coordinates.local_translation = translation;
+        return;
+    }
+
+    let shift = -delta
+        .translation(settings.render_cell_size(&scale))
+        .as_f32();
+    let old_cell = origin.cell;
+    origin.cell += delta;
+    coordinates.cell = origin.cell;
+    coordinates.local_translation = translation + shift;
+    rebase_events.send(OrginRebasingEvent::new(old_cell, origin.cell, &shift));
+
+    // Children are relative to their parent, shifting them as well would move them twice
+    for mut transform in q.q1_mut().iter_mut() {
+        transform.translation += shift;
+    }
+}
+
+/// The cell the anchor started in, drawn as a wireframe cube that moves with the world.
+struct DebugCell {
+    centre: Vec3,
 }
 
-fn setup(mut lines: ResMut<DebugLines>) {
-    let line1 = Line::new(
-        Vec3::new(-MAX_BOUND, MAX_BOUND, -MAX_BOUND),
-        Vec3::new(MAX_BOUND, MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::RED,
-        Color::RED,
-    );
-    let line2 = Line::new(
-        Vec3::new(-MAX_BOUND, MAX_BOUND, MAX_BOUND),
-        Vec3::new(MAX_BOUND, MAX_BOUND, MAX_BOUND),
-        0.0,
-        Color::GREEN,
-        Color::GREEN,
-    );
-    let line3 = Line::new(
-        Vec3::new(-MAX_BOUND, MAX_BOUND, MAX_BOUND),
-        Vec3::new(-MAX_BOUND, MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::BLUE,
-        Color::BLUE,
-    );
-    let line4 = Line::new(
-        Vec3::new(MAX_BOUND, MAX_BOUND, MAX_BOUND),
-        Vec3::new(MAX_BOUND, MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::WHITE,
-        Color::WHITE,
-    );
-    lines.user_lines.push(line1);
-    lines.user_lines.push(line2);
-    lines.user_lines.push(line3);
-    lines.user_lines.push(line4);
-
-    let line1 = Line::new(
-        Vec3::new(-MAX_BOUND, -MAX_BOUND, -MAX_BOUND),
-        Vec3::new(MAX_BOUND, -MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::RED,
-        Color::RED,
-    );
-    let line2 = Line::new(
-        Vec3::new(-MAX_BOUND, -MAX_BOUND, MAX_BOUND),
-        Vec3::new(MAX_BOUND, -MAX_BOUND, MAX_BOUND),
-        0.0,
-        Color::GREEN,
-        Color::GREEN,
-    );
-    let line3 = Line::new(
-        Vec3::new(-MAX_BOUND, -MAX_BOUND, MAX_BOUND),
-        Vec3::new(-MAX_BOUND, -MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::BLUE,
-        Color::BLUE,
-    );
-    let line4 = Line::new(
-        Vec3::new(MAX_BOUND, -MAX_BOUND, MAX_BOUND),
-        Vec3::new(MAX_BOUND, -MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::WHITE,
-        Color::WHITE,
-    );
-    lines.user_lines.push(line1);
-    lines.user_lines.push(line2);
-    lines.user_lines.push(line3);
-    lines.user_lines.push(line4);
-
-    let line1 = Line::new(
-        Vec3::new(-MAX_BOUND, MAX_BOUND, -MAX_BOUND),
-        Vec3::new(-MAX_BOUND, -MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::RED,
-        Color::RED,
-    );
-    let line2 = Line::new(
-        Vec3::new(MAX_BOUND, MAX_BOUND, -MAX_BOUND),
-        Vec3::new(MAX_BOUND, -MAX_BOUND, -MAX_BOUND),
-        0.0,
-        Color::GREEN,
-        Color::GREEN,
-    );
-    let line3 = Line::new(
-        Vec3::new(-MAX_BOUND, MAX_BOUND, MAX_BOUND),
-        Vec3::new(-MAX_BOUND, -MAX_BOUND, MAX_BOUND),
-        0.0,
-        Color::BLUE,
-        Color::BLUE,
-    );
-    let line4 = Line::new(
-        Vec3::new(MAX_BOUND, MAX_BOUND, MAX_BOUND),
-        Vec3::new(MAX_BOUND, -MAX_BOUND, MAX_BOUND),
-        0.0,
-        Color::WHITE,
-        Color::WHITE,
-    );
-    lines.user_lines.push(line1);
-    lines.user_lines.push(line2);
-    lines.user_lines.push(line3);
-    lines.user_lines.push(line4);
+impl Rebaseable for DebugCell {
+    fn rebase(&mut self, translation: Vec3) {
+        self.centre += translation;
+    }
+}
+
+fn draw_debug_cell(
+    settings: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    cell: Res<DebugCell>,
+    mut lines: ResMut<DebugLines>,
+) {
+    let bound = 0.5 * settings.render_cell_size(&scale) as f32;
+    let corner = |x: f32, y: f32, z: f32| cell.centre + Vec3::new(x, y, z) * bound;
+    let colors = [Color::RED, Color::GREEN, Color::BLUE, Color::WHITE];
+    for y in [1.0, -1.0] {
+        let edges = [
+            (corner(-1.0, y, -1.0), corner(1.0, y, -1.0)),
+            (corner(-1.0, y, 1.0), corner(1.0, y, 1.0)),
+            (corner(-1.0, y, 1.0), corner(-1.0, y, -1.0)),
+            (corner(1.0, y, 1.0), corner(1.0, y, -1.0)),
+        ];
+        for ((start, end), color) in edges.iter().zip(colors) {
+            lines.line_colored(*start, *end, 0.0, color);
+        }
+    }
+    let verticals = [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)];
+    for ((x, z), color) in verticals.iter().zip(colors) {
+        lines.line_colored(corner(*x, 1.0, *z), corner(*x, -1.0, *z), 0.0, color);
+    }
 }
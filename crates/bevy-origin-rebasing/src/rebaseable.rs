@@ -0,0 +1,59 @@
+use crate::{OrginRebasingEvent, SYNC_SIMULATION_COORDINATES_SYSTEM};
+use bevy::prelude::*;
+
+pub const REBASE_STATE_SYSTEM: &str = "rebase_state";
+
+/// World-space state kept outside of `Transform`, e.g. particles, trajectory buffers or debug
+/// lines. Once registered with `RebaseableAppExt` it is shifted by every `OrginRebasingEvent`.
+pub trait Rebaseable: Send + Sync + 'static {
+    fn rebase(&mut self, translation: Vec3);
+}
+
+pub trait RebaseableAppExt {
+    /// Shifts every `T` component on each rebase.
+    fn register_rebaseable_component<T: Rebaseable>(&mut self) -> &mut Self;
+    /// Shifts the `T` resource, if there is one, on each rebase.
+    fn register_rebaseable_resource<T: Rebaseable>(&mut self) -> &mut Self;
+}
+
+impl RebaseableAppExt for AppBuilder {
+    fn register_rebaseable_component<T: Rebaseable>(&mut self) -> &mut Self {
+        self.add_system(
+            rebase_components::<T>
+                .system()
+                .label(REBASE_STATE_SYSTEM)
+                .after(SYNC_SIMULATION_COORDINATES_SYSTEM),
+        )
+    }
+
+    fn register_rebaseable_resource<T: Rebaseable>(&mut self) -> &mut Self {
+        self.add_system(
+            rebase_resource::<T>
+                .system()
+                .label(REBASE_STATE_SYSTEM)
+                .after(SYNC_SIMULATION_COORDINATES_SYSTEM),
+        )
+    }
+}
+
+fn rebase_components<T: Rebaseable>(
+    mut events: EventReader<OrginRebasingEvent>,
+    mut query: Query<&mut T>,
+) {
+    for event in events.iter() {
+        for mut state in query.iter_mut() {
+            state.rebase(**event);
+        }
+    }
+}
+
+fn rebase_resource<T: Rebaseable>(
+    mut events: EventReader<OrginRebasingEvent>,
+    mut resource: Option<ResMut<T>>,
+) {
+    for event in events.iter() {
+        if let Some(resource) = resource.as_mut() {
+            resource.rebase(**event);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::cell::GridCell;
+use bevy::prelude::*;
+use bevy_world_scale::WorldScale;
+
+/// Distances are in metres and go through `WorldScale`, so the same settings work whatever the
+/// render scale is.
+#[derive(Debug, Clone, Copy)]
+pub struct OriginRebasingSettings {
+    /// Edge length of a rebasing cell.
+    pub cell_size: f64,
+    /// Entities with `SceneCompression` further than this from the anchor are pulled in to it
+    /// and shrunk.
+    pub view_distance: f64,
+    /// How far the anchor has to go past a cell's edge before rebasing, so moving back and forth
+    /// across the edge doesn't rebase every frame.
+    pub hysteresis: f64,
+    pub draw_debug_cell: bool,
+}
+
+impl Default for OriginRebasingSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 2_000.0,
+            view_distance: 10_000.0,
+            hysteresis: 0.0,
+            draw_debug_cell: true,
+        }
+    }
+}
+
+impl OriginRebasingSettings {
+    /// Cell size in render units.
+    pub fn render_cell_size(&self, scale: &WorldScale) -> f64 {
+        self.cell_size * scale.0
+    }
+
+    /// Whole cells a render translation has moved past the current cell on each axis, more than
+    /// one if it was teleported. Zero on every axis while it is within the cell plus hysteresis.
+    pub fn cells_crossed(&self, translation: Vec3, scale: &WorldScale) -> GridCell {
+        let cell_size = self.render_cell_size(scale) as f32;
+        let bound = 0.5 * cell_size + scale.length(self.hysteresis);
+        let cells = |value: f32| {
+            if value.abs() > bound {
+                (value / cell_size).round() as i64
+            } else {
+                0
+            }
+        };
+        GridCell::new(
+            cells(translation.x),
+            cells(translation.y),
+            cells(translation.z),
+        )
+    }
+}
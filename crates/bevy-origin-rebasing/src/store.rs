@@ -0,0 +1,100 @@
+use crate::GridCell;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::PathBuf;
+
+/// Everything needed to respawn a streamed entity. `kind` and `data` come from its `Streamed`
+/// component and are up to the game, e.g. a prefab name and its serialized state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamedEntity {
+    pub local_translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+    pub kind: String,
+    pub data: String,
+}
+
+/// Where entities of unloaded cells are kept until the anchor comes back.
+pub trait CellStore: Send + Sync + 'static {
+    /// Adds entities to whatever is already stored for `cell`.
+    fn store(&mut self, cell: GridCell, entities: Vec<StreamedEntity>) -> io::Result<()>;
+    /// Removes and returns everything stored for `cell`.
+    fn take(&mut self, cell: GridCell) -> io::Result<Vec<StreamedEntity>>;
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryStore(pub HashMap<GridCell, Vec<StreamedEntity>>);
+
+impl CellStore for MemoryStore {
+    fn store(&mut self, cell: GridCell, entities: Vec<StreamedEntity>) -> io::Result<()> {
+        self.0.entry(cell).or_default().extend(entities);
+        Ok(())
+    }
+
+    fn take(&mut self, cell: GridCell) -> io::Result<Vec<StreamedEntity>> {
+        Ok(self.0.remove(&cell).unwrap_or_default())
+    }
+}
+
+/// One JSON file per cell in `directory`, so a world can be streamed across sessions.
+#[derive(Debug)]
+pub struct FileStore {
+    pub directory: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, cell: GridCell) -> PathBuf {
+        self.directory
+            .join(format!("{}_{}_{}.json", cell.x, cell.y, cell.z))
+    }
+
+    fn read(&self, cell: GridCell) -> io::Result<Vec<StreamedEntity>> {
+        match File::open(self.path(cell)) {
+            Ok(file) => Ok(serde_json::from_reader(BufReader::new(file))?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl CellStore for FileStore {
+    fn store(&mut self, cell: GridCell, entities: Vec<StreamedEntity>) -> io::Result<()> {
+        let mut stored = self.read(cell)?;
+        stored.extend(entities);
+        fs::create_dir_all(&self.directory)?;
+        let file = File::create(self.path(cell))?;
+        Ok(serde_json::to_writer(BufWriter::new(file), &stored)?)
+    }
+
+    fn take(&mut self, cell: GridCell) -> io::Result<Vec<StreamedEntity>> {
+        let stored = self.read(cell)?;
+        if !stored.is_empty() {
+            fs::remove_file(self.path(cell))?;
+        }
+        Ok(stored)
+    }
+}
+
+/// The store used by `CellStreamingPlugin`, an in-memory one unless inserted beforehand.
+pub struct CellStorage(pub Box<dyn CellStore>);
+
+impl CellStorage {
+    pub fn new(store: impl CellStore) -> Self {
+        Self(Box::new(store))
+    }
+}
+
+impl Default for CellStorage {
+    fn default() -> Self {
+        Self::new(MemoryStore::default())
+    }
+}
@@ -0,0 +1,155 @@
+use crate::events::{CellEnteredEvent, CellExitedEvent};
+use crate::store::{CellStorage, StreamedEntity};
+use crate::{
+    FloatingOrigin, GridCell, OriginRebasingSettings, SimulationCoordinates,
+    SYNC_SIMULATION_COORDINATES_SYSTEM,
+};
+use bevy::prelude::*;
+use bevy_world_scale::WorldScale;
+use std::collections::HashMap;
+
+pub const STREAM_CELLS_SYSTEM: &str = "stream_cells";
+
+#[derive(Debug, Clone, Copy)]
+pub struct CellStreamingSettings {
+    /// Cells further than this from the anchor's cell on any axis are unloaded.
+    pub radius: i64,
+}
+
+impl Default for CellStreamingSettings {
+    fn default() -> Self {
+        Self { radius: 4 }
+    }
+}
+
+/// Marks a root entity that is despawned into the `CellStorage` when its cell leaves the
+/// streaming radius, and respawned with this component, its `Transform` and
+/// `SimulationCoordinates` when the cell comes back. Rebuilding meshes and the rest from `kind`
+/// and `data` is up to the game, e.g. with an `Added<Streamed>` query.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Streamed {
+    pub kind: String,
+    pub data: String,
+}
+
+/// Streams `Streamed` entities by grid cell around the `FloatingOriginAnchor`. Needs the
+/// `OriginRebasingPlugin`.
+#[derive(Default)]
+pub struct CellStreamingPlugin {
+    pub settings: CellStreamingSettings,
+}
+
+impl CellStreamingPlugin {
+    pub fn new(settings: CellStreamingSettings) -> Self {
+        Self { settings }
+    }
+}
+
+impl Plugin for CellStreamingPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<CellEnteredEvent>()
+            .add_event::<CellExitedEvent>()
+            .insert_resource(self.settings)
+            .init_resource::<CellStorage>()
+            .add_system(
+                stream_cells
+                    .system()
+                    .label(STREAM_CELLS_SYSTEM)
+                    .after(SYNC_SIMULATION_COORDINATES_SYSTEM),
+            );
+    }
+}
+
+fn cells_around(centre: GridCell, radius: i64) -> impl Iterator<Item = GridCell> {
+    (-radius..=radius).flat_map(move |x| {
+        (-radius..=radius)
+            .flat_map(move |y| (-radius..=radius).map(move |z| centre + GridCell::new(x, y, z)))
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn stream_cells(
+    mut commands: Commands,
+    settings: Res<CellStreamingSettings>,
+    rebasing: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    origin: Res<FloatingOrigin>,
+    mut storage: ResMut<CellStorage>,
+    mut loaded_around: Local<Option<GridCell>>,
+    mut entered_events: EventWriter<CellEnteredEvent>,
+    mut exited_events: EventWriter<CellExitedEvent>,
+    query: Query<(Entity, &Transform, &Streamed), Without<Parent>>,
+) {
+    if origin.anchor.is_none() {
+        return;
+    }
+    let cell_size = rebasing.render_cell_size(&scale);
+    let radius = settings.radius;
+    let in_range = |cell: GridCell, centre: GridCell| {
+        let offset = cell - centre;
+        offset.x.abs() <= radius && offset.y.abs() <= radius && offset.z.abs() <= radius
+    };
+
+    let mut unloaded: HashMap<GridCell, Vec<StreamedEntity>> = HashMap::new();
+    for (entity, transform, streamed) in query.iter() {
+        let (offset, local_translation) =
+            GridCell::from_translation(transform.translation.as_f64(), cell_size);
+        let cell = origin.cell + offset;
+        if in_range(cell, origin.cell) {
+            continue;
+        }
+        unloaded.entry(cell).or_default().push(StreamedEntity {
+            local_translation: local_translation.as_f32(),
+            rotation: transform.rotation,
+            scale: transform.scale,
+            kind: streamed.kind.clone(),
+            data: streamed.data.clone(),
+        });
+        commands.entity(entity).despawn_recursive();
+    }
+    for (cell, entities) in unloaded {
+        if let Err(err) = storage.0.store(cell, entities) {
+            error!("Failed to store streamed cell {:?}: {}", cell, err);
+        }
+    }
+
+    if *loaded_around == Some(origin.cell) {
+        return;
+    }
+    let previous = loaded_around.replace(origin.cell);
+    if let Some(previous) = previous {
+        for cell in cells_around(previous, radius).filter(|cell| !in_range(*cell, origin.cell)) {
+            exited_events.send(CellExitedEvent::new(cell));
+        }
+    }
+    for cell in cells_around(origin.cell, radius) {
+        if matches!(previous, Some(previous) if in_range(cell, previous)) {
+            continue;
+        }
+        let entities = match storage.0.take(cell) {
+            Ok(entities) => entities,
+            Err(err) => {
+                error!("Failed to load streamed cell {:?}: {}", cell, err);
+                Vec::new()
+            }
+        };
+        for entity in entities {
+            let coordinates = SimulationCoordinates::new(cell, entity.local_translation);
+            let origin_coordinates = SimulationCoordinates::new(origin.cell, Vec3::ZERO);
+            commands.spawn_bundle((
+                Transform {
+                    translation: coordinates.relative_translation(&origin_coordinates, cell_size),
+                    rotation: entity.rotation,
+                    scale: entity.scale,
+                },
+                GlobalTransform::identity(),
+                coordinates,
+                Streamed {
+                    kind: entity.kind,
+                    data: entity.data,
+                },
+            ));
+        }
+        entered_events.send(CellEnteredEvent::new(cell));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{GridCell, OriginRebasingSettings, SimulationCoordinates};
+use bevy::prelude::*;
+use bevy_world_scale::WorldScale;
+
+/// Gives a camera its own floating origin, for split-screen views or render-to-texture passes.
+/// The camera's `Transform` is its offset within `cell` and is re-celled like the main anchor, so
+/// it stays near the origin of its own frame wherever the shared origin is. It draws entities
+/// through their `ViewInstance`s. The camera must be a root entity.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ViewOrigin {
+    pub cell: GridCell,
+}
+
+impl ViewOrigin {
+    pub fn new(cell: GridCell) -> Self {
+        Self { cell }
+    }
+
+    /// Where `coordinates` renders in this view.
+    pub fn render_translation(&self, coordinates: &SimulationCoordinates, cell_size: f64) -> Vec3 {
+        ((coordinates.cell() - self.cell).translation(cell_size)
+            + coordinates.local_translation().as_f64())
+        .as_f32()
+    }
+}
+
+/// Draws `source` in the frame of the `view` camera. Put it on a root entity without children that
+/// has the mesh, material and pass tags or `RenderLayers` only that view draws, one per view.
+/// Its `GlobalTransform` is placed every frame from the source's `SimulationCoordinates` relative
+/// to the view's cell, with the rotation and scale of the source. The source keeps its shared
+/// `Transform`, so the main camera and any number of views can draw it at once.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewInstance {
+    pub view: Entity,
+    pub source: Entity,
+}
+
+impl ViewInstance {
+    pub fn new(view: Entity, source: Entity) -> Self {
+        Self { view, source }
+    }
+}
+
+pub(crate) fn update_view_origins(
+    settings: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    mut views: Query<(&mut ViewOrigin, &mut Transform)>,
+) {
+    let cell_size = settings.render_cell_size(&scale);
+
+    for (mut origin, mut transform) in views.iter_mut() {
+        let delta = settings.cells_crossed(transform.translation, &scale);
+        if delta != GridCell::ZERO {
+            origin.cell += delta;
+            transform.translation -= delta.translation(cell_size).as_f32();
+        }
+    }
+}
+
+/// Runs after transform propagation, which would otherwise place instances by their `Transform`.
+pub(crate) fn place_view_instances(
+    settings: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    views: Query<&ViewOrigin>,
+    sources: Query<(&SimulationCoordinates, &GlobalTransform), Without<ViewInstance>>,
+    mut instances: Query<(&ViewInstance, &mut GlobalTransform)>,
+) {
+    let cell_size = settings.render_cell_size(&scale);
+
+    for (instance, mut global) in instances.iter_mut() {
+        if let (Ok(origin), Ok((coordinates, source))) =
+            (views.get(instance.view), sources.get(instance.source))
+        {
+            *global = GlobalTransform {
+                translation: origin.render_translation(coordinates, cell_size),
+                ..*source
+            };
+        }
+    }
+}
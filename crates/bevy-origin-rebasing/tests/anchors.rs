@@ -0,0 +1,146 @@
+mod common;
+
+use bevy::app::Events;
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+use common::*;
+
+fn spawn(app: &mut AppBuilder, translation: Vec3) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_translation(translation),
+            GlobalTransform::from_translation(translation),
+            SimulationCoordinates::default(),
+        ))
+        .id()
+}
+
+fn origin(app: &mut AppBuilder) -> FloatingOrigin {
+    *app.world_mut().get_resource::<FloatingOrigin>().unwrap()
+}
+
+fn rebase_count(app: &mut AppBuilder) -> usize {
+    let events = app
+        .world_mut()
+        .get_resource::<Events<OrginRebasingEvent>>()
+        .unwrap();
+    events.get_reader().iter(events).count()
+}
+
+#[test]
+fn no_anchor_leaves_the_world_alone() {
+    let mut app = app();
+    let planet = spawn(&mut app, Vec3::new(5_000.0, 0.0, 0.0));
+
+    app.app.update();
+    app.app.update();
+
+    assert_eq!(translation(&mut app, planet), Vec3::new(5_000.0, 0.0, 0.0));
+    assert_eq!(origin(&mut app).anchor, None);
+    assert_eq!(origin(&mut app).cell, GridCell::ZERO);
+    assert_eq!(rebase_count(&mut app), 0);
+}
+
+#[test]
+fn anchor_without_coordinates_is_ignored() {
+    let mut app = app();
+    let player = spawn(&mut app, Vec3::new(1_500.0, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    app.app.update();
+
+    let marker = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((Transform::identity(), FloatingOriginAnchor))
+        .id();
+    app.app.update();
+
+    assert_eq!(origin(&mut app).anchor, Some(player));
+    assert_eq!(translation(&mut app, marker), Vec3::ZERO);
+}
+
+#[test]
+fn anchor_can_be_removed_and_added_back() {
+    let mut app = app();
+    let player = spawn(&mut app, Vec3::new(1_500.0, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    app.app.update();
+    assert_eq!(origin(&mut app).cell, GridCell::new(1, 0, 0));
+
+    app.world_mut()
+        .entity_mut(player)
+        .remove::<FloatingOriginAnchor>();
+    app.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = Vec3::new(1_500.0, 0.0, 0.0);
+    app.app.update();
+    assert_eq!(origin(&mut app).anchor, None);
+    assert_eq!(translation(&mut app, player), Vec3::new(1_500.0, 0.0, 0.0));
+
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    app.app.update();
+    assert_eq!(origin(&mut app).anchor, Some(player));
+    assert_eq!(origin(&mut app).cell, GridCell::new(2, 0, 0));
+    assert_eq!(translation(&mut app, player), Vec3::new(-500.0, 0.0, 0.0));
+}
+
+#[test]
+fn switching_anchors_does_not_move_anything() {
+    let mut app = app();
+    let player = spawn(&mut app, Vec3::new(1_500.0, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    let spectator = spawn(&mut app, Vec3::new(2_300.0, 0.0, 0.0));
+    app.app.update();
+    assert_eq!(translation(&mut app, spectator), Vec3::new(300.0, 0.0, 0.0));
+
+    // The newest anchor wins, the old one may keep its component
+    app.world_mut()
+        .entity_mut(spectator)
+        .insert(FloatingOriginAnchor);
+    app.app.update();
+
+    assert_eq!(origin(&mut app).anchor, Some(spectator));
+    assert_eq!(origin(&mut app).cell, GridCell::new(1, 0, 0));
+    assert_eq!(translation(&mut app, player), Vec3::new(-500.0, 0.0, 0.0));
+    assert_eq!(translation(&mut app, spectator), Vec3::new(300.0, 0.0, 0.0));
+    assert_eq!(
+        *app.world_mut()
+            .get::<SimulationCoordinates>(spectator)
+            .unwrap(),
+        SimulationCoordinates::new(GridCell::new(1, 0, 0), Vec3::new(300.0, 0.0, 0.0))
+    );
+    assert_eq!(rebase_count(&mut app), 1);
+}
+
+#[test]
+fn switching_to_a_distant_anchor_rebases_on_it() {
+    let mut app = app();
+    let player = spawn(&mut app, Vec3::ZERO);
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    let ship = spawn(&mut app, Vec3::new(0.0, 0.0, -4_100.0));
+    app.app.update();
+
+    app.world_mut()
+        .entity_mut(player)
+        .remove::<FloatingOriginAnchor>();
+    app.world_mut()
+        .entity_mut(ship)
+        .insert(FloatingOriginAnchor);
+    app.app.update();
+
+    assert_eq!(origin(&mut app).cell, GridCell::new(0, 0, -2));
+    assert_eq!(translation(&mut app, ship), Vec3::new(0.0, 0.0, -100.0));
+    assert_eq!(translation(&mut app, player), Vec3::new(0.0, 0.0, 4_000.0));
+}
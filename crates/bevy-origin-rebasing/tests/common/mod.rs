@@ -0,0 +1,34 @@
+// Shared by the integration tests, each of which only uses some of it
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy::transform::TransformPlugin;
+use bevy_origin_rebasing::*;
+
+pub const CELL_SIZE: f64 = 2_000.0;
+
+pub fn app() -> AppBuilder {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(TransformPlugin)
+        .add_plugin(OriginRebasingPlugin::new(OriginRebasingSettings {
+            cell_size: CELL_SIZE,
+            draw_debug_cell: false,
+            ..Default::default()
+        }));
+    app
+}
+
+pub fn translation(app: &mut AppBuilder, entity: Entity) -> Vec3 {
+    app.world_mut()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation
+}
+
+pub fn global_translation(app: &mut AppBuilder, entity: Entity) -> Vec3 {
+    app.world_mut()
+        .get::<GlobalTransform>(entity)
+        .unwrap()
+        .translation
+}
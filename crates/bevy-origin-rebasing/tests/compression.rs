@@ -0,0 +1,180 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+use common::*;
+
+fn spawn_anchor(app: &mut AppBuilder, translation: Vec3) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_translation(translation),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+            FloatingOriginAnchor,
+        ))
+        .id()
+}
+
+fn spawn_compressed(app: &mut AppBuilder, translation: Vec3, scale: f32) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform {
+                translation,
+                scale: Vec3::splat(scale),
+                ..Default::default()
+            },
+            GlobalTransform::identity(),
+            SimulationCoordinates::from_translation(translation.as_f64(), CELL_SIZE),
+            SceneCompression,
+        ))
+        .id()
+}
+
+fn transform(app: &mut AppBuilder, entity: Entity) -> Transform {
+    *app.world_mut().get::<Transform>(entity).unwrap()
+}
+
+fn global(app: &mut AppBuilder, entity: Entity) -> GlobalTransform {
+    *app.world_mut().get::<GlobalTransform>(entity).unwrap()
+}
+
+#[test]
+fn far_objects_keep_direction_and_angular_size() {
+    let mut app = app();
+    spawn_anchor(&mut app, Vec3::new(0.0, 0.0, 600.0));
+    // 30 km out along -z from the anchor and 40 km along +x
+    let planet = spawn_compressed(&mut app, Vec3::new(40_000.0, 0.0, -29_400.0), 2.0);
+
+    app.app.update();
+
+    let compressed = global(&mut app, planet);
+    let anchor = Vec3::new(0.0, 0.0, 600.0);
+    let offset = compressed.translation - anchor;
+    assert!((offset.length() - 10_000.0).abs() < 1e-2);
+    assert!((offset.normalize() - Vec3::new(0.8, 0.0, -0.6)).length() < 1e-6);
+    // Radius over distance stays 2 / 50 km
+    assert!((compressed.scale.x / offset.length() - 2.0 / 50_000.0).abs() < 1e-9);
+    assert_eq!(
+        transform(&mut app, planet).translation,
+        Vec3::new(40_000.0, 0.0, -29_400.0)
+    );
+}
+
+#[test]
+fn near_objects_keep_their_scale() {
+    let mut app = app();
+    spawn_anchor(&mut app, Vec3::ZERO);
+    let moon = spawn_compressed(&mut app, Vec3::new(7_500.0, 0.0, 0.0), 3.0);
+
+    app.app.update();
+
+    assert_eq!(
+        global(&mut app, moon),
+        GlobalTransform {
+            translation: Vec3::new(7_500.0, 0.0, 0.0),
+            scale: Vec3::splat(3.0),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn moving_a_compressed_object_is_kept() {
+    let mut app = app();
+    spawn_anchor(&mut app, Vec3::ZERO);
+    let planet = spawn_compressed(&mut app, Vec3::new(20_000.0, 0.0, 0.0), 1.0);
+    app.app.update();
+    app.app.update();
+    assert_eq!(global(&mut app, planet).scale, Vec3::splat(0.5));
+
+    app.world_mut()
+        .get_mut::<Transform>(planet)
+        .unwrap()
+        .translation
+        .x += 20_000.0;
+    app.app.update();
+    app.app.update();
+
+    assert_eq!(
+        transform(&mut app, planet).translation,
+        Vec3::new(40_000.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        global(&mut app, planet).translation,
+        Vec3::new(10_000.0, 0.0, 0.0)
+    );
+    assert_eq!(global(&mut app, planet).scale, Vec3::splat(0.25));
+}
+
+#[test]
+fn children_are_compressed_with_their_root() {
+    let mut app = app();
+    spawn_anchor(&mut app, Vec3::ZERO);
+    let mut moon = None;
+    let planet = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_xyz(20_000.0, 0.0, 0.0),
+            GlobalTransform::identity(),
+            SceneCompression,
+        ))
+        .with_children(|parent| {
+            moon = Some(
+                parent
+                    .spawn_bundle((
+                        Transform::from_xyz(0.0, 4_000.0, 0.0),
+                        GlobalTransform::identity(),
+                    ))
+                    .id(),
+            );
+        })
+        .id();
+    app.app.update();
+    app.app.update();
+
+    assert_eq!(
+        global(&mut app, planet).translation,
+        Vec3::new(10_000.0, 0.0, 0.0)
+    );
+    let moon = global(&mut app, moon.unwrap());
+    assert_eq!(moon.translation, Vec3::new(10_000.0, 2_000.0, 0.0));
+    assert_eq!(moon.scale, Vec3::splat(0.5));
+}
+
+#[test]
+fn placement_follows_the_anchor_every_frame() {
+    let mut app = app();
+    let anchor = spawn_anchor(&mut app, Vec3::ZERO);
+    let planet = spawn_compressed(&mut app, Vec3::new(24_000.0, 0.0, 0.0), 1.0);
+    app.app.update();
+    assert_eq!(
+        global(&mut app, planet).translation,
+        Vec3::new(10_000.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        global(&mut app, planet).scale,
+        Vec3::splat(10_000.0 / 24_000.0)
+    );
+
+    // Crossing into the next cell rebases, the planet is still placed from the anchor
+    app.world_mut()
+        .get_mut::<Transform>(anchor)
+        .unwrap()
+        .translation = Vec3::new(5_000.0, 0.0, 0.0);
+    app.app.update();
+    assert_eq!(
+        transform(&mut app, anchor).translation,
+        Vec3::new(-1_000.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        global(&mut app, planet).translation,
+        Vec3::new(9_000.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        global(&mut app, planet).scale,
+        Vec3::splat(10_000.0 / 19_000.0)
+    );
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+use common::*;
+
+fn app() -> AppBuilder {
+    let mut app = common::app();
+    app.add_plugin(PrecisionDiagnosticsPlugin::new(
+        PrecisionDiagnosticsSettings { max_ulp: 0.0001 },
+    ));
+    app
+}
+
+fn spawn(app: &mut AppBuilder, translation: Vec3) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_translation(translation),
+            GlobalTransform::identity(),
+            SimulationCoordinates::from_translation(translation.as_f64(), CELL_SIZE),
+        ))
+        .id()
+}
+
+fn precision(app: &mut AppBuilder, entity: Entity) -> TranslationPrecision {
+    *app.world_mut().get::<TranslationPrecision>(entity).unwrap()
+}
+
+fn histogram(app: &mut AppBuilder) -> PrecisionHistogram {
+    app.world_mut()
+        .get_resource::<PrecisionHistogram>()
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn ulp_is_the_gap_to_the_next_float() {
+    assert_eq!(ulp(1.0), f32::EPSILON);
+    assert_eq!(ulp(-3_000.0), 2f32.powi(-12));
+    assert_eq!(ulp(1.0e7), 1.0);
+}
+
+#[test]
+fn entities_are_checked_against_the_precision_budget() {
+    let mut app = app();
+    let near = spawn(&mut app, Vec3::new(0.0, 100.0, 0.0));
+    let far = spawn(&mut app, Vec3::new(3_000.0, 0.0, 0.0));
+    app.app.update();
+
+    assert_eq!(
+        precision(&mut app, near),
+        TranslationPrecision {
+            distance: 100.0,
+            ulp: 2f32.powi(-17),
+            within_budget: true,
+        }
+    );
+    assert!(!precision(&mut app, far).within_budget);
+    let counts = histogram(&mut app);
+    assert_eq!(
+        counts.buckets.into_iter().collect::<Vec<_>>(),
+        vec![(-17, 1), (-12, 1)]
+    );
+    assert_eq!(counts.over_budget, 1);
+
+    app.world_mut()
+        .get_mut::<Transform>(far)
+        .unwrap()
+        .translation = Vec3::new(0.0, 0.0, 100.0);
+    app.app.update();
+
+    assert!(precision(&mut app, far).within_budget);
+    assert_eq!(histogram(&mut app).buckets.get(&-17), Some(&2));
+    assert_eq!(histogram(&mut app).over_budget, 0);
+}
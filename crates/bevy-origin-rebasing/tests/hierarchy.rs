@@ -0,0 +1,157 @@
+mod common;
+
+use bevy::app::Events;
+use bevy::prelude::*;
+use bevy::render::camera::Camera;
+use bevy_origin_rebasing::*;
+use common::*;
+
+fn spawn_player(app: &mut AppBuilder, translation: Vec3) -> (Entity, Entity) {
+    let world = app.world_mut();
+    let camera = world
+        .spawn()
+        .insert_bundle(PerspectiveCameraBundle {
+            transform: Transform::from_xyz(0.0, 1.0, 8.0),
+            ..PerspectiveCameraBundle::new_3d()
+        })
+        .insert(SimulationCoordinates::default())
+        .id();
+    let player = world
+        .spawn()
+        .insert_bundle((
+            Transform::from_translation(translation),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+            FloatingOriginAnchor,
+        ))
+        .push_children(&[camera])
+        .id();
+    (player, camera)
+}
+
+fn rebase_events(app: &mut AppBuilder) -> Vec<(GridCell, GridCell, Vec3)> {
+    let events = app
+        .world_mut()
+        .get_resource::<Events<OrginRebasingEvent>>()
+        .unwrap();
+    events
+        .get_reader()
+        .iter(events)
+        .map(|event| (event.old_cell, event.new_cell, event.translation))
+        .collect()
+}
+
+#[test]
+fn parented_entities_are_shifted_once() {
+    let mut app = app();
+    let (player, camera) = spawn_player(&mut app, Vec3::new(1_500.0, 0.0, 0.0));
+
+    let mut mesh = None;
+    let mut light = None;
+    let ship = app
+        .world_mut()
+        .spawn()
+        .insert_bundle(PbrBundle {
+            transform: Transform::from_xyz(100.0, 0.0, -50.0),
+            ..Default::default()
+        })
+        .insert(SimulationCoordinates::default())
+        .with_children(|parent| {
+            mesh = Some(
+                parent
+                    .spawn_bundle(PbrBundle {
+                        transform: Transform::from_xyz(0.0, 5.0, 0.0),
+                        ..Default::default()
+                    })
+                    .insert(SimulationCoordinates::default())
+                    .id(),
+            );
+            light = Some(
+                parent
+                    .spawn_bundle(LightBundle {
+                        transform: Transform::from_xyz(0.0, 0.0, 2.0),
+                        ..Default::default()
+                    })
+                    .id(),
+            );
+        })
+        .id();
+    let (mesh, light) = (mesh.unwrap(), light.unwrap());
+
+    app.app.update();
+
+    assert_eq!(translation(&mut app, player), Vec3::new(-500.0, 0.0, 0.0));
+    assert_eq!(translation(&mut app, ship), Vec3::new(-1_900.0, 0.0, -50.0));
+    assert_eq!(translation(&mut app, mesh), Vec3::new(0.0, 5.0, 0.0));
+    assert_eq!(translation(&mut app, light), Vec3::new(0.0, 0.0, 2.0));
+    assert_eq!(translation(&mut app, camera), Vec3::new(0.0, 1.0, 8.0));
+    assert_eq!(
+        global_translation(&mut app, mesh),
+        Vec3::new(-1_900.0, 5.0, -50.0)
+    );
+    assert_eq!(
+        global_translation(&mut app, light),
+        Vec3::new(-1_900.0, 0.0, -48.0)
+    );
+    assert_eq!(
+        global_translation(&mut app, camera),
+        Vec3::new(-500.0, 1.0, 8.0)
+    );
+}
+
+#[test]
+fn flagged_roots_are_shifted() {
+    let mut app = app();
+    spawn_player(&mut app, Vec3::new(0.0, 0.0, -1_200.0));
+
+    let world = app.world_mut();
+    let sun = world
+        .spawn()
+        .insert_bundle(LightBundle {
+            transform: Transform::from_xyz(10.0, 0.0, 0.0),
+            ..Default::default()
+        })
+        .insert(RebaseRoot)
+        .id();
+    let skybox_camera = world
+        .spawn()
+        .insert_bundle(PerspectiveCameraBundle::new_3d())
+        .id();
+
+    app.app.update();
+
+    assert_eq!(translation(&mut app, sun), Vec3::new(10.0, 0.0, 2_000.0));
+    assert_eq!(translation(&mut app, skybox_camera), Vec3::ZERO);
+    let mut cameras = app.world_mut().query::<&Camera>();
+    assert_eq!(cameras.iter(app.world_mut()).count(), 2);
+}
+
+#[test]
+fn diagonal_crossing_sends_one_event() {
+    let mut app = app();
+    let (player, _) = spawn_player(&mut app, Vec3::new(1_500.0, 10.0, -2_600.0));
+
+    app.app.update();
+
+    assert_eq!(
+        rebase_events(&mut app),
+        vec![(
+            GridCell::ZERO,
+            GridCell::new(1, 0, -1),
+            Vec3::new(-2_000.0, 0.0, 2_000.0)
+        )]
+    );
+    assert_eq!(
+        translation(&mut app, player),
+        Vec3::new(-500.0, 10.0, -600.0)
+    );
+    let coordinates = *app
+        .world_mut()
+        .get::<SimulationCoordinates>(player)
+        .unwrap();
+    assert_eq!(coordinates.cell(), GridCell::new(1, 0, -1));
+    assert_eq!(
+        coordinates.local_translation(),
+        Vec3::new(-500.0, 10.0, -600.0)
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+use common::*;
+
+struct Trail(Vec<Vec3>);
+
+impl Rebaseable for Trail {
+    fn rebase(&mut self, translation: Vec3) {
+        for point in self.0.iter_mut() {
+            *point += translation;
+        }
+    }
+}
+
+#[derive(Default)]
+struct ParticleCache {
+    origin: Vec3,
+}
+
+impl Rebaseable for ParticleCache {
+    fn rebase(&mut self, translation: Vec3) {
+        self.origin += translation;
+    }
+}
+
+#[test]
+fn registered_state_is_shifted_on_rebase() {
+    let mut app = app();
+    app.register_rebaseable_component::<Trail>()
+        .register_rebaseable_resource::<ParticleCache>()
+        .insert_resource(ParticleCache {
+            origin: Vec3::new(0.0, 10.0, 0.0),
+        });
+    let player = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_xyz(0.0, 1_200.0, 0.0),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+            FloatingOriginAnchor,
+        ))
+        .id();
+    let trail = app
+        .world_mut()
+        .spawn()
+        .insert(Trail(vec![Vec3::ZERO, Vec3::new(0.0, 1_200.0, 0.0)]))
+        .id();
+
+    app.app.update();
+
+    assert_eq!(
+        app.world_mut().get::<Trail>(trail).unwrap().0,
+        vec![Vec3::new(0.0, -2_000.0, 0.0), Vec3::new(0.0, -800.0, 0.0)]
+    );
+    assert_eq!(
+        app.world_mut()
+            .get_resource::<ParticleCache>()
+            .unwrap()
+            .origin,
+        Vec3::new(0.0, -1_990.0, 0.0)
+    );
+
+    // Nothing moves again until the next rebase
+    app.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = Vec3::ZERO;
+    app.app.update();
+    assert_eq!(
+        app.world_mut().get::<Trail>(trail).unwrap().0[1],
+        Vec3::new(0.0, -800.0, 0.0)
+    );
+}
+
+#[test]
+fn missing_resources_are_skipped() {
+    let mut app = app();
+    app.register_rebaseable_resource::<ParticleCache>();
+    app.world_mut().spawn().insert_bundle((
+        Transform::from_xyz(-3_000.0, 0.0, 0.0),
+        GlobalTransform::identity(),
+        SimulationCoordinates::default(),
+        FloatingOriginAnchor,
+    ));
+
+    app.app.update();
+
+    assert!(app.world_mut().get_resource::<ParticleCache>().is_none());
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use bevy::app::Events;
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+
+fn app() -> AppBuilder {
+    let mut app = common::app();
+    app.add_plugin(CellStreamingPlugin::new(CellStreamingSettings {
+        radius: 1,
+    }));
+    app
+}
+
+fn cells<T: std::ops::Deref<Target = GridCell> + Send + Sync + 'static>(
+    app: &mut AppBuilder,
+) -> Vec<GridCell> {
+    let events = app.world_mut().get_resource::<Events<T>>().unwrap();
+    let mut reader = events.get_reader();
+    let mut cells: Vec<GridCell> = reader.iter(events).map(|event| **event).collect();
+    cells.sort_by_key(|cell| (cell.x, cell.y, cell.z));
+    cells
+}
+
+fn streamed(app: &mut AppBuilder) -> Vec<(Vec3, Streamed)> {
+    let world = app.world_mut();
+    world
+        .query::<(&Transform, &Streamed)>()
+        .iter(world)
+        .map(|(transform, streamed)| (transform.translation, streamed.clone()))
+        .collect()
+}
+
+#[test]
+fn entities_are_stored_and_restored_with_their_cells() {
+    let mut app = app();
+    let player = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::identity(),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+            FloatingOriginAnchor,
+        ))
+        .id();
+    let asteroid = Streamed {
+        kind: "asteroid".to_string(),
+        data: "{\"ore\":12}".to_string(),
+    };
+    app.world_mut().spawn().insert_bundle((
+        Transform::from_xyz(5_200.0, 0.0, 0.0),
+        GlobalTransform::identity(),
+        asteroid.clone(),
+    ));
+
+    app.app.update();
+    assert!(streamed(&mut app).is_empty());
+    assert_eq!(cells::<CellEnteredEvent>(&mut app).len(), 27);
+
+    // Two cells over, which brings the asteroid's cell back into range
+    app.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = Vec3::new(3_100.0, 0.0, 0.0);
+    app.app.update();
+
+    assert_eq!(
+        streamed(&mut app),
+        vec![(Vec3::new(1_200.0, 0.0, 0.0), asteroid)]
+    );
+    let exited = cells::<CellExitedEvent>(&mut app);
+    assert_eq!(exited.len(), 18);
+    assert!(exited.iter().all(|cell| cell.x == -1 || cell.x == 0));
+}
+
+#[test]
+fn file_store_round_trips_cells() {
+    let directory = std::env::temp_dir().join(format!("cell-store-{}", std::process::id()));
+    let mut store = FileStore::new(&directory);
+    let cell = GridCell::new(-3, 0, 1_000_000_000_000);
+    let entity = |kind: &str| StreamedEntity {
+        local_translation: Vec3::new(1.0, 2.0, 3.0),
+        rotation: Quat::from_rotation_y(0.5),
+        scale: Vec3::splat(2.0),
+        kind: kind.to_string(),
+        data: String::new(),
+    };
+
+    store.store(cell, vec![entity("station")]).unwrap();
+    store.store(cell, vec![entity("probe")]).unwrap();
+
+    assert_eq!(
+        store.take(cell).unwrap(),
+        vec![entity("station"), entity("probe")]
+    );
+    assert!(store.take(cell).unwrap().is_empty());
+    std::fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use bevy::app::Events;
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+use common::*;
+
+fn spawn(app: &mut AppBuilder, translation: Vec3) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_translation(translation),
+            GlobalTransform::identity(),
+            SimulationCoordinates::from_translation(translation.as_f64(), CELL_SIZE),
+        ))
+        .id()
+}
+
+fn teleport(app: &mut AppBuilder, entity: Entity, translation: DVec3) {
+    app.world_mut()
+        .get_mut::<SimulationCoordinates>(entity)
+        .unwrap()
+        .teleport(translation);
+}
+
+fn coordinates(app: &mut AppBuilder, entity: Entity) -> SimulationCoordinates {
+    *app.world_mut()
+        .get::<SimulationCoordinates>(entity)
+        .unwrap()
+}
+
+fn rebases(app: &mut AppBuilder) -> Vec<(GridCell, GridCell, Vec3)> {
+    let events = app
+        .world_mut()
+        .get_resource::<Events<OrginRebasingEvent>>()
+        .unwrap();
+    events
+        .get_reader()
+        .iter(events)
+        .map(|event| (event.old_cell, event.new_cell, event.translation))
+        .collect()
+}
+
+#[test]
+fn teleporting_an_entity_places_it_relative_to_the_origin() {
+    let mut app = app();
+    let player = spawn(&mut app, Vec3::new(2_500.0, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    let probe = spawn(&mut app, Vec3::ZERO);
+    app.app.update();
+
+    // One AU out, in render units of one per metre
+    teleport(&mut app, probe, DVec3::new(0.0, 0.0, 149_597_870_691.0));
+    app.app.update();
+
+    let expected =
+        SimulationCoordinates::new(GridCell::new(0, 0, 74_798_935), Vec3::new(0.0, 0.0, 691.0));
+    assert_eq!(coordinates(&mut app, probe), expected);
+    assert_eq!(
+        translation(&mut app, probe),
+        expected.relative_translation(&coordinates(&mut app, player), CELL_SIZE)
+    );
+    assert_eq!(coordinates(&mut app, probe).pending_teleport(), None);
+}
+
+#[test]
+fn teleporting_the_anchor_rebases_the_world() {
+    let mut app = app();
+    let player = spawn(&mut app, Vec3::new(100.0, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(player)
+        .insert(FloatingOriginAnchor);
+    let station = spawn(&mut app, Vec3::new(300.0, 0.0, 0.0));
+    app.app.update();
+
+    teleport(&mut app, player, DVec3::new(-1_000_050.0, 0.0, 0.0));
+    app.app.update();
+
+    assert_eq!(
+        coordinates(&mut app, player),
+        SimulationCoordinates::new(GridCell::new(-500, 0, 0), Vec3::new(-50.0, 0.0, 0.0))
+    );
+    assert_eq!(translation(&mut app, player), Vec3::new(-50.0, 0.0, 0.0));
+    assert_eq!(
+        translation(&mut app, station),
+        Vec3::new(1_000_300.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        rebases(&mut app),
+        vec![(
+            GridCell::ZERO,
+            GridCell::new(-500, 0, 0),
+            Vec3::new(1_000_000.0, 0.0, 0.0)
+        )]
+    );
+}
+
+#[test]
+fn teleporting_a_child_is_dropped() {
+    let mut app = app();
+    let mut antenna = None;
+    let station = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_xyz(300.0, 0.0, 0.0),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+        ))
+        .with_children(|parent| {
+            antenna = Some(
+                parent
+                    .spawn_bundle((
+                        Transform::from_xyz(0.0, 10.0, 0.0),
+                        GlobalTransform::identity(),
+                        SimulationCoordinates::default(),
+                    ))
+                    .id(),
+            );
+        })
+        .id();
+    let antenna = antenna.unwrap();
+    app.app.update();
+
+    teleport(&mut app, antenna, DVec3::new(50_000.0, 0.0, 0.0));
+    app.app.update();
+
+    assert_eq!(coordinates(&mut app, antenna).pending_teleport(), None);
+    assert_eq!(translation(&mut app, antenna), Vec3::new(0.0, 10.0, 0.0));
+    assert_eq!(translation(&mut app, station), Vec3::new(300.0, 0.0, 0.0));
+}
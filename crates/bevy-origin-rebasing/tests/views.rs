@@ -0,0 +1,118 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_origin_rebasing::*;
+use common::*;
+
+fn spawn_view(app: &mut AppBuilder, cell: GridCell, translation: Vec3) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle(PerspectiveCameraBundle {
+            transform: Transform::from_translation(translation),
+            ..PerspectiveCameraBundle::new_3d()
+        })
+        .insert(ViewOrigin::new(cell))
+        .id()
+}
+
+fn spawn_instance(app: &mut AppBuilder, view: Entity, source: Entity) -> Entity {
+    app.world_mut()
+        .spawn()
+        .insert_bundle(PbrBundle::default())
+        .insert(ViewInstance::new(view, source))
+        .id()
+}
+
+#[test]
+fn views_draw_the_same_source_precisely_from_their_own_cells() {
+    let mut app = app();
+    let coordinates =
+        SimulationCoordinates::new(GridCell::new(1_000_000, 0, 0), Vec3::new(0.25, 0.0, 0.0));
+    // Relative to the shared origin the quarter unit is lost, f32 steps are 256 out here
+    let shared = coordinates.relative_translation(&SimulationCoordinates::default(), CELL_SIZE);
+    assert_eq!(shared, Vec3::new(2_000_000_000.0, 0.0, 0.0));
+    let rotation = Quat::from_rotation_y(1.0);
+    let source = app
+        .world_mut()
+        .spawn()
+        .insert_bundle(PbrBundle {
+            transform: Transform {
+                translation: shared,
+                rotation,
+                scale: Vec3::splat(3.0),
+            },
+            ..Default::default()
+        })
+        .insert(coordinates)
+        .id();
+    let left = spawn_view(&mut app, GridCell::new(1_000_000, 0, 0), Vec3::ZERO);
+    let right = spawn_view(&mut app, GridCell::new(1_000_000, 0, -3), Vec3::ZERO);
+    let in_left = spawn_instance(&mut app, left, source);
+    let in_right = spawn_instance(&mut app, right, source);
+
+    app.app.update();
+
+    // Both views draw the source near their own origin and leave its shared transform alone
+    assert_eq!(translation(&mut app, source), shared);
+    assert_eq!(global_translation(&mut app, left), Vec3::ZERO);
+    assert_eq!(global_translation(&mut app, right), Vec3::ZERO);
+    assert_eq!(
+        global_translation(&mut app, in_left),
+        Vec3::new(0.25, 0.0, 0.0)
+    );
+    assert_eq!(
+        global_translation(&mut app, in_right),
+        Vec3::new(0.25, 0.0, 6_000.0)
+    );
+    let global = app.world_mut().get::<GlobalTransform>(in_right).unwrap();
+    assert_eq!(global.rotation, rotation);
+    assert_eq!(global.scale, Vec3::splat(3.0));
+}
+
+#[test]
+fn views_rebase_on_their_own() {
+    let mut app = app();
+    let player = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_xyz(1_500.0, 0.0, 0.0),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+            FloatingOriginAnchor,
+        ))
+        .id();
+    let body = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::from_xyz(0.0, 0.0, 2_400.0),
+            GlobalTransform::identity(),
+            SimulationCoordinates::new(GridCell::new(0, 0, 1), Vec3::new(0.0, 0.0, 400.0)),
+        ))
+        .id();
+    let view = spawn_view(&mut app, GridCell::ZERO, Vec3::new(0.0, 0.0, 2_500.0));
+    let instance = spawn_instance(&mut app, view, body);
+
+    app.app.update();
+
+    // The shared rebase moves the player and the body, the view re-cells around its own offset
+    assert_eq!(translation(&mut app, player), Vec3::new(-500.0, 0.0, 0.0));
+    assert_eq!(
+        translation(&mut app, body),
+        Vec3::new(-2_000.0, 0.0, 2_400.0)
+    );
+    assert_eq!(translation(&mut app, view), Vec3::new(0.0, 0.0, 500.0));
+    assert_eq!(
+        app.world_mut().get::<ViewOrigin>(view).unwrap().cell,
+        GridCell::new(0, 0, 1)
+    );
+    assert_eq!(
+        global_translation(&mut app, view),
+        Vec3::new(0.0, 0.0, 500.0)
+    );
+    assert_eq!(
+        global_translation(&mut app, instance),
+        Vec3::new(0.0, 0.0, 400.0)
+    );
+}
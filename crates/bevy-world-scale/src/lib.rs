@@ -0,0 +1,29 @@
+use bevy::{math::DVec3, prelude::*};
+
+pub const METERS_PER_KILOMETER: f64 = 1_000.0;
+pub const METERS_PER_ASTRONOMICAL_UNIT: f64 = 149_597_870_691.0;
+
+/// Render units per metre. Everything that turns simulated distances into translations, mesh
+/// sizes or distance thresholds goes through this, so it can be changed without a rebuild.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldScale(pub f64);
+
+impl Default for WorldScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl WorldScale {
+    pub fn length(&self, metres: f64) -> f32 {
+        (metres * self.0) as f32
+    }
+
+    pub fn position(&self, metres: DVec3) -> Vec3 {
+        (metres * self.0).as_f32()
+    }
+
+    pub fn to_metres(&self, units: f32) -> f64 {
+        units as f64 / self.0
+    }
+}
@@ -8,10 +8,11 @@ use bevy::{
     window::WindowId,
 };
 use space::scale::*;
+use space::units::*;
 
 pub struct FirstPassCube;
 
-const EARTH_RADIUS: f32 = 6051.84 * KM_TO_UNIT_SCALE;
+const EARTH_RADIUS: Kilometers = Kilometers(6051.84);
 
 fn main() {
     App::build()
@@ -33,11 +34,12 @@ fn setup(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    // let size = EARTH_RADIUS * 10000.0;
-    // let distance = -1.0 * AU_TO_UNIT_SCALE;
+    let scale = WorldScale::default();
+    // let size = scale.to_render_units(EARTH_RADIUS * 10000.0);
+    // let distance = -scale.to_render_units(AstronomicalUnits(1.0));
 
-    let size = EARTH_RADIUS;
-    let distance = -10.0 * EARTH_RADIUS;
+    let size = scale.to_render_units(EARTH_RADIUS);
+    let distance = -10.0 * size;
 
     let cube_handle = meshes.add(Mesh::from(shape::Cube { size }));
     let cube_material_handle = materials.add(StandardMaterial {
@@ -62,8 +64,8 @@ fn setup(
         transform: Transform::from_translation(Vec3::new(0.0, 0.0, 15.0))
             .looking_at(Vec3::default(), Vec3::Y),
         perspective_projection: PerspectiveProjection {
-            far: 10.0 * AU_TO_UNIT_SCALE,
-            // near: 0.5 * AU_TO_UNIT_SCALE,
+            far: scale.to_render_units(AstronomicalUnits(10.0)),
+            // near: scale.to_render_units(AstronomicalUnits(0.5)),
             ..Default::default()
         },
         ..Default::default()
@@ -76,4 +78,4 @@ fn rotator_system(time: Res<Time>, mut query: Query<&mut Transform, With<FirstPa
         transform.rotation *= Quat::from_rotation_x(1.5 * time.delta_seconds());
         transform.rotation *= Quat::from_rotation_z(1.3 * time.delta_seconds());
     }
-}
\ No newline at end of file
+}
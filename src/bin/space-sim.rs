@@ -0,0 +1,176 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use space::gravity::body::*;
+use space::gravity::nbody::*;
+use space::gravity::resources::*;
+use space::gravity::system::SystemDefinition;
+use space::trajectory::*;
+use space::units::Seconds;
+use std::io;
+
+const USAGE: &str = "\
+Runs the gravity simulation without a window and writes sampled state vectors.
+
+USAGE:
+    space-sim [OPTIONS]
+
+OPTIONS:
+    --system <path>          System definition JSON file [default: built-in solar system]
+    --duration <days>        Simulated duration in days [default: 365.25]
+    --timestep <seconds>     Simulated seconds per step [default: 3600]
+    --integrator <name>      euler, semi-implicit-euler or leapfrog [default: leapfrog]
+    --post-newtonian         Add the 1PN correction even if the system does not ask for it
+    --sample-every <steps>   Steps between samples [default: 24]
+    --format <format>        csv or jsonl [default: csv]
+    --output <path>          Output file [default: stdout]";
+
+struct HeadlessSettings {
+    duration: Seconds,
+    sample_every: u64,
+}
+
+struct Args {
+    system: Option<String>,
+    duration: Seconds,
+    timestep: Seconds,
+    integrator: Integrator,
+    post_newtonian: bool,
+    sample_every: u64,
+    format: TrajectoryFormat,
+    output: Option<String>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            system: None,
+            duration: Seconds::from_days(365.25),
+            timestep: Seconds(3_600.0),
+            integrator: Integrator::Leapfrog,
+            post_newtonian: false,
+            sample_every: 24,
+            format: TrajectoryFormat::Csv,
+            output: None,
+        }
+    }
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        if flag == "-h" || flag == "--help" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        if flag == "--post-newtonian" {
+            args.post_newtonian = true;
+            continue;
+        }
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?;
+        let invalid = || format!("Invalid value '{}' for {}", value, flag);
+        match flag.as_str() {
+            "--system" => args.system = Some(value.clone()),
+            "--duration" => {
+                args.duration = Seconds::from_days(value.parse().map_err(|_| invalid())?)
+            }
+            "--timestep" => args.timestep = Seconds(value.parse().map_err(|_| invalid())?),
+            "--integrator" => args.integrator = value.parse()?,
+            "--sample-every" => args.sample_every = value.parse().map_err(|_| invalid())?,
+            "--format" => args.format = value.parse()?,
+            "--output" => args.output = Some(value.clone()),
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    if args.timestep <= Seconds(0.0) || args.sample_every == 0 {
+        return Err("--timestep and --sample-every must be positive".to_string());
+    }
+    Ok(args)
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        std::process::exit(2);
+    });
+
+    let system = match &args.system {
+        Some(path) => SystemDefinition::load(path).expect("Failed to load system definition"),
+        None => SystemDefinition::solar_system(),
+    };
+
+    let writer = match &args.output {
+        Some(path) => TrajectoryWriter::create(path, args.format).expect("Failed to create output"),
+        None => TrajectoryWriter::new(io::stdout(), args.format),
+    };
+
+    App::build()
+        .insert_resource(SimulationSettings {
+            timestep: args.timestep,
+            integrator: args.integrator,
+        })
+        .insert_resource(PostNewtonian(system.post_newtonian || args.post_newtonian))
+        .insert_resource(SimulationClock {
+            start: system.epoch,
+            ..Default::default()
+        })
+        .insert_resource(HeadlessSettings {
+            duration: args.duration,
+            sample_every: args.sample_every,
+        })
+        .insert_resource(system)
+        .insert_resource(writer)
+        .add_plugins(MinimalPlugins)
+        .add_plugin(NBodyPlugin)
+        .add_startup_system(spawn_system.system())
+        .add_startup_system_to_stage(StartupStage::PostStartup, record_samples.system())
+        .add_system(
+            record_samples
+                .system()
+                .label("record_samples")
+                .after(FINISH_STEP_SYSTEM),
+        )
+        .add_system(exit_after_duration.system().after("record_samples"))
+        .run();
+}
+
+fn spawn_system(mut commands: Commands, system: Res<SystemDefinition>) {
+    system.spawn(&mut commands);
+}
+
+fn record_samples(
+    clock: Res<SimulationClock>,
+    settings: Res<HeadlessSettings>,
+    mut writer: ResMut<TrajectoryWriter>,
+    query: Query<(&Name, &Position, &Velocity)>,
+) {
+    let finished = clock.elapsed >= settings.duration.0;
+    if !clock.steps.is_multiple_of(settings.sample_every) && !finished {
+        return;
+    }
+    for (name, pos, vel) in query.iter() {
+        writer
+            .write(&TrajectorySample {
+                time: clock.elapsed,
+                epoch: clock.epoch(),
+                body: name.as_str(),
+                position: pos.0,
+                velocity: vel.0,
+            })
+            .expect("Failed to write trajectory sample");
+    }
+}
+
+fn exit_after_duration(
+    clock: Res<SimulationClock>,
+    settings: Res<HeadlessSettings>,
+    mut writer: ResMut<TrajectoryWriter>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if clock.elapsed >= settings.duration.0 {
+        writer.flush().expect("Failed to flush trajectory output");
+        exit.send(AppExit);
+    }
+}
@@ -0,0 +1,481 @@
+use crate::units::SECONDS_PER_DAY;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+pub const J2000_JULIAN_DATE: f64 = 2_451_545.0;
+const UNIX_EPOCH_JULIAN_DATE: f64 = 2_440_587.5;
+const TT_MINUS_TAI: f64 = 32.184;
+
+/// TAI - UTC in seconds, effective from 00:00 UTC on the given date (days since 1970-01-01).
+/// Dates before 1972 use the initial 10 s offset, the fractional pre-1972 rates are not modelled.
+#[rustfmt::skip]
+const LEAP_SECONDS: [(i64, f64); 28] = [
+    (730, 10.0),   // 1972-01-01
+    (912, 11.0),   // 1972-07-01
+    (1096, 12.0),  // 1973-01-01
+    (1461, 13.0),  // 1974-01-01
+    (1826, 14.0),  // 1975-01-01
+    (2191, 15.0),  // 1976-01-01
+    (2557, 16.0),  // 1977-01-01
+    (2922, 17.0),  // 1978-01-01
+    (3287, 18.0),  // 1979-01-01
+    (3652, 19.0),  // 1980-01-01
+    (4199, 20.0),  // 1981-07-01
+    (4564, 21.0),  // 1982-07-01
+    (4929, 22.0),  // 1983-07-01
+    (5660, 23.0),  // 1985-07-01
+    (6574, 24.0),  // 1988-01-01
+    (7305, 25.0),  // 1990-01-01
+    (7670, 26.0),  // 1991-01-01
+    (8217, 27.0),  // 1992-07-01
+    (8582, 28.0),  // 1993-07-01
+    (8947, 29.0),  // 1994-07-01
+    (9496, 30.0),  // 1996-01-01
+    (10043, 31.0), // 1997-07-01
+    (10592, 32.0), // 1999-01-01
+    (13149, 33.0), // 2006-01-01
+    (14245, 34.0), // 2009-01-01
+    (15522, 35.0), // 2012-07-01
+    (16617, 36.0), // 2015-07-01
+    (17167, 37.0), // 2017-01-01
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    Utc,
+    Tai,
+    Tt,
+    Tdb,
+}
+
+impl FromStr for TimeScale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "UTC" | "Z" => Ok(TimeScale::Utc),
+            "TAI" => Ok(TimeScale::Tai),
+            "TT" => Ok(TimeScale::Tt),
+            "TDB" => Ok(TimeScale::Tdb),
+            _ => Err(format!("Unknown time scale '{}'", s)),
+        }
+    }
+}
+
+/// A calendar date and time of day. `second` can reach 60 during a UTC leap second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: f64,
+}
+
+impl DateTime {
+    fn days_since_unix_epoch(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+
+    fn seconds_of_day(&self) -> f64 {
+        (self.hour * 3_600 + self.minute * 60) as f64 + self.second
+    }
+
+    fn from_days_and_seconds(days: i64, seconds: f64) -> Self {
+        let (year, month, day) = civil_from_days(days);
+        // Only a leap second pushes `seconds` past the end of the day
+        let (hour, minute, second) = if seconds >= SECONDS_PER_DAY {
+            (23, 59, seconds - SECONDS_PER_DAY + 60.0)
+        } else {
+            let whole = seconds.floor() as u32;
+            (
+                whole / 3_600,
+                whole / 60 % 60,
+                seconds - (whole - whole % 60) as f64,
+            )
+        };
+        Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        }
+    }
+}
+
+/// An instant in time, stored as TDB seconds since J2000.
+/// Serialized as an ISO 8601 TDB string with millisecond precision.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Epoch {
+    tdb_seconds_since_j2000: f64,
+}
+
+impl Default for Epoch {
+    fn default() -> Self {
+        Self::J2000
+    }
+}
+
+impl Epoch {
+    pub const J2000: Epoch = Epoch {
+        tdb_seconds_since_j2000: 0.0,
+    };
+
+    pub fn from_tdb_seconds_since_j2000(seconds: f64) -> Self {
+        Self {
+            tdb_seconds_since_j2000: seconds,
+        }
+    }
+
+    pub fn tdb_seconds_since_j2000(&self) -> f64 {
+        self.tdb_seconds_since_j2000
+    }
+
+    pub fn from_julian_date(jd: f64, scale: TimeScale) -> Self {
+        let seconds = (jd - J2000_JULIAN_DATE) * SECONDS_PER_DAY;
+        match scale {
+            TimeScale::Tdb => Self::from_tdb_seconds_since_j2000(seconds),
+            TimeScale::Tt => Self::from_tt(seconds),
+            TimeScale::Tai => Self::from_tt(seconds + TT_MINUS_TAI),
+            TimeScale::Utc => {
+                let days = (jd - UNIX_EPOCH_JULIAN_DATE).floor();
+                let seconds_of_day = (jd - UNIX_EPOCH_JULIAN_DATE - days) * SECONDS_PER_DAY;
+                Self::from_utc_days_and_seconds(days as i64, seconds_of_day)
+            }
+        }
+    }
+
+    pub fn julian_date(&self, scale: TimeScale) -> f64 {
+        let seconds = match scale {
+            TimeScale::Tdb => self.tdb_seconds_since_j2000,
+            TimeScale::Tt => self.tt(),
+            TimeScale::Tai => self.tt() - TT_MINUS_TAI,
+            TimeScale::Utc => {
+                let (days, seconds_of_day) = self.utc_days_and_seconds();
+                return UNIX_EPOCH_JULIAN_DATE + days as f64 + seconds_of_day / SECONDS_PER_DAY;
+            }
+        };
+        J2000_JULIAN_DATE + seconds / SECONDS_PER_DAY
+    }
+
+    pub fn from_date_time(date_time: DateTime, scale: TimeScale) -> Self {
+        let days = date_time.days_since_unix_epoch();
+        let seconds_of_day = date_time.seconds_of_day();
+        match scale {
+            TimeScale::Utc => Self::from_utc_days_and_seconds(days, seconds_of_day),
+            _ => {
+                let jd = UNIX_EPOCH_JULIAN_DATE + days as f64;
+                let start_of_day = Self::from_julian_date(jd, scale);
+                start_of_day + seconds_of_day
+            }
+        }
+    }
+
+    pub fn date_time(&self, scale: TimeScale) -> DateTime {
+        match scale {
+            TimeScale::Utc => {
+                let (days, seconds_of_day) = self.utc_days_and_seconds();
+                DateTime::from_days_and_seconds(days, seconds_of_day)
+            }
+            _ => {
+                let days_since_unix_epoch = self.julian_date(scale) - UNIX_EPOCH_JULIAN_DATE;
+                let days = days_since_unix_epoch.floor();
+                let seconds = (days_since_unix_epoch - days) * SECONDS_PER_DAY;
+                DateTime::from_days_and_seconds(days as i64, seconds)
+            }
+        }
+    }
+
+    /// Formats as ISO 8601 with millisecond precision, suffixed with `Z` for UTC or the scale name.
+    pub fn to_iso8601(&self, scale: TimeScale) -> String {
+        let mut date_time = self.date_time(scale);
+        let mut millis = (date_time.second * 1_000.0).round() as u32;
+        let leap = date_time.hour == 23 && date_time.minute == 59 && date_time.second >= 60.0;
+        if (millis >= 60_000 && !leap) || millis >= 61_000 {
+            // Rounding spilled into the next minute, let the calendar carry it
+            let rounded = *self + (60.0 - date_time.second) + if leap { 1.0 } else { 0.0 };
+            date_time = rounded.date_time(scale);
+            millis = (date_time.second * 1_000.0).round() as u32;
+        }
+        let suffix = match scale {
+            TimeScale::Utc => "Z",
+            TimeScale::Tai => " TAI",
+            TimeScale::Tt => " TT",
+            TimeScale::Tdb => " TDB",
+        };
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}{}",
+            date_time.year,
+            date_time.month,
+            date_time.day,
+            date_time.hour,
+            date_time.minute,
+            millis / 1_000,
+            millis % 1_000,
+            suffix
+        )
+    }
+
+    /// Parses `YYYY-MM-DD[THH:MM[:SS[.fff]]][Z|±HH:MM| UTC| TAI| TT| TDB]`. No suffix means UTC.
+    /// Only four digit years from 0000 to 9999 are accepted, negative and expanded years are not.
+    /// `:60` is only accepted at 23:59 UTC, without an offset, on a day that ends in a leap second.
+    pub fn parse_iso8601(s: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid ISO 8601 date '{}'", s);
+        let s = s.trim();
+
+        let (s, scale) = match s.rsplit_once(' ') {
+            Some((rest, suffix)) if suffix.parse::<TimeScale>().is_ok() => {
+                (rest.trim_end(), suffix.parse()?)
+            }
+            _ => (s, TimeScale::Utc),
+        };
+
+        let (date, time) = match s.find(['T', 't', ' ']) {
+            Some(index) => (&s[..index], &s[index + 1..]),
+            None => (s, ""),
+        };
+
+        let (time, offset_minutes) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+            (time, 0)
+        } else if let Some(index) = time.rfind(['+', '-']) {
+            let sign = if time[index..].starts_with('-') {
+                -1
+            } else {
+                1
+            };
+            let mut parts = time[index + 1..].splitn(2, ':');
+            let hours: i64 = parts.next().unwrap_or("").parse().map_err(|_| invalid())?;
+            let minutes: i64 = parts.next().unwrap_or("0").parse().map_err(|_| invalid())?;
+            (&time[..index], sign * (hours * 60 + minutes))
+        } else {
+            (time, 0)
+        };
+        if offset_minutes != 0 && scale != TimeScale::Utc {
+            return Err(invalid());
+        }
+
+        let mut date_parts = date.splitn(3, '-');
+        let mut next_date_part = || date_parts.next().ok_or_else(invalid);
+        let year = next_date_part()?;
+        if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let year: i64 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = next_date_part()?.parse().map_err(|_| invalid())?;
+        let day: u32 = next_date_part()?.parse().map_err(|_| invalid())?;
+
+        let mut time_parts = time.splitn(3, ':');
+        let hour: u32 = match time_parts.next() {
+            Some(hour) if !hour.is_empty() => hour.parse().map_err(|_| invalid())?,
+            _ => 0,
+        };
+        let minute: u32 = time_parts
+            .next()
+            .unwrap_or("0")
+            .parse()
+            .map_err(|_| invalid())?;
+        let second: f64 = time_parts
+            .next()
+            .unwrap_or("0")
+            .parse()
+            .map_err(|_| invalid())?;
+
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || !(0.0..61.0).contains(&second)
+        {
+            return Err(invalid());
+        }
+        let leap_second = scale == TimeScale::Utc
+            && offset_minutes == 0
+            && hour == 23
+            && minute == 59
+            && ends_with_leap_second(days_from_civil(year, month, day));
+        if second >= 60.0 && !leap_second {
+            return Err(invalid());
+        }
+
+        let epoch = Self::from_date_time(
+            DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+            },
+            scale,
+        );
+        Ok(epoch - (offset_minutes * 60) as f64)
+    }
+
+    fn from_tt(tt_seconds_since_j2000: f64) -> Self {
+        Self::from_tdb_seconds_since_j2000(
+            tt_seconds_since_j2000 + tdb_minus_tt(tt_seconds_since_j2000),
+        )
+    }
+
+    fn tt(&self) -> f64 {
+        // TDB - TT is under 2 ms, so evaluating it at TDB instead of TT is accurate to nanoseconds
+        self.tdb_seconds_since_j2000 - tdb_minus_tt(self.tdb_seconds_since_j2000)
+    }
+
+    /// TAI seconds since 1970-01-01T00:00:00 TAI.
+    fn tai_since_unix_epoch(&self) -> f64 {
+        let j2000_since_unix_epoch = (J2000_JULIAN_DATE - UNIX_EPOCH_JULIAN_DATE) * SECONDS_PER_DAY;
+        self.tt() - TT_MINUS_TAI + j2000_since_unix_epoch
+    }
+
+    fn from_utc_days_and_seconds(days: i64, seconds_of_day: f64) -> Self {
+        let tai = days as f64 * SECONDS_PER_DAY + seconds_of_day + tai_minus_utc(days);
+        let j2000_since_unix_epoch = (J2000_JULIAN_DATE - UNIX_EPOCH_JULIAN_DATE) * SECONDS_PER_DAY;
+        Self::from_tt(tai + TT_MINUS_TAI - j2000_since_unix_epoch)
+    }
+
+    fn utc_days_and_seconds(&self) -> (i64, f64) {
+        let tai = self.tai_since_unix_epoch();
+        let mut offset = LEAP_SECONDS[0].1;
+        let mut next_change = None;
+        for &(day, value) in LEAP_SECONDS.iter() {
+            if tai >= day as f64 * SECONDS_PER_DAY + value {
+                offset = value;
+            } else {
+                next_change = Some(day);
+                break;
+            }
+        }
+        let utc = tai - offset;
+        if let Some(day) = next_change {
+            let start_of_day = day as f64 * SECONDS_PER_DAY;
+            if utc >= start_of_day {
+                // Inside the inserted 23:59:60 of the previous day
+                return (day - 1, SECONDS_PER_DAY + (utc - start_of_day));
+            }
+        }
+        let days = (utc / SECONDS_PER_DAY).floor();
+        (days as i64, utc - days * SECONDS_PER_DAY)
+    }
+}
+
+impl Add<f64> for Epoch {
+    type Output = Epoch;
+
+    fn add(self, seconds: f64) -> Self::Output {
+        Self::from_tdb_seconds_since_j2000(self.tdb_seconds_since_j2000 + seconds)
+    }
+}
+
+impl Sub<f64> for Epoch {
+    type Output = Epoch;
+
+    fn sub(self, seconds: f64) -> Self::Output {
+        Self::from_tdb_seconds_since_j2000(self.tdb_seconds_since_j2000 - seconds)
+    }
+}
+
+impl Sub for Epoch {
+    type Output = f64;
+
+    fn sub(self, other: Epoch) -> Self::Output {
+        self.tdb_seconds_since_j2000 - other.tdb_seconds_since_j2000
+    }
+}
+
+impl FromStr for Epoch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_iso8601(s)
+    }
+}
+
+impl TryFrom<String> for Epoch {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse_iso8601(&s)
+    }
+}
+
+impl From<Epoch> for String {
+    fn from(epoch: Epoch) -> Self {
+        epoch.to_iso8601(TimeScale::Tdb)
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_iso8601(TimeScale::Utc))
+    }
+}
+
+fn tai_minus_utc(days_since_unix_epoch: i64) -> f64 {
+    LEAP_SECONDS
+        .iter()
+        .rev()
+        .find(|(day, _)| days_since_unix_epoch >= *day)
+        .map_or(LEAP_SECONDS[0].1, |(_, value)| *value)
+}
+
+/// Whether a UTC day, in days since 1970-01-01, ends with an inserted 23:59:60.
+fn ends_with_leap_second(days_since_unix_epoch: i64) -> bool {
+    LEAP_SECONDS[1..]
+        .iter()
+        .any(|(day, _)| *day == days_since_unix_epoch + 1)
+}
+
+/// Periodic TDB - TT difference from the Explanatory Supplement, accurate to ~30 µs.
+fn tdb_minus_tt(seconds_since_j2000: f64) -> f64 {
+    let days = seconds_since_j2000 / SECONDS_PER_DAY;
+    let g = (357.53 + 0.985_600_28 * days).to_radians();
+    0.001_657 * g.sin() + 0.000_013_85 * (2.0 * g).sin()
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Proleptic Gregorian conversions from http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -1,28 +1,283 @@
-use bevy::prelude::*;
+use crate::gravity::resources::G;
+use crate::units::*;
+use bevy::{math::DVec3, prelude::*};
+use bevy_origin_rebasing::SimulationCoordinates;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
-struct Mass(f32);
+/// Mass of a body, zero for bodies that are attracted but don't attract anything themselves.
+pub struct Mass(pub Kilograms);
 
-#[derive(Default)]
-struct Velocity(Vec3);
+// Vector counterparts of the quantities in `units`. Each adds and subtracts only with itself,
+// acceleration times `Seconds` is a velocity and velocity times `Seconds` a position.
+macro_rules! vector_quantity {
+    ($(#[$doc:meta])* $name:ident, $scalar:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub DVec3);
 
-#[derive(Default)]
-struct Acceleration(Vec3);
+        impl $name {
+            pub const ZERO: Self = Self(DVec3::ZERO);
 
+            pub fn new(x: f64, y: f64, z: f64) -> Self {
+                Self(DVec3::new(x, y, z))
+            }
+
+            pub fn length(&self) -> $scalar {
+                $scalar(self.0.length())
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+    };
+}
+
+vector_quantity!(
+    /// Position relative to the simulation origin, in metres.
+    Position,
+    Meters
+);
+vector_quantity!(
+    /// Velocity in metres per second.
+    Velocity,
+    MetersPerSecond
+);
+vector_quantity!(
+    /// Acceleration in metres per second squared, accumulated by the force systems every step.
+    Acceleration,
+    MetersPerSecondSquared
+);
+
+impl Position {
+    pub fn distance(&self, other: Position) -> Meters {
+        (*self - other).length()
+    }
+}
+
+impl Mul<Seconds> for Velocity {
+    type Output = Position;
+
+    fn mul(self, rhs: Seconds) -> Position {
+        Position(self.0 * rhs.0)
+    }
+}
+
+impl Mul<Seconds> for Acceleration {
+    type Output = Velocity;
+
+    fn mul(self, rhs: Seconds) -> Velocity {
+        Velocity(self.0 * rhs.0)
+    }
+}
+
+/// Zonal harmonic coefficients of a non-spherical body, e.g. Earth has `j2 = 1.08263e-3`.
+/// `reference_radius` is the equatorial radius the coefficients were fitted at.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ZonalHarmonics {
+    pub j2: f64,
+    #[serde(default)]
+    pub j3: f64,
+    pub reference_radius: Meters,
+}
+
+/// Unit rotation axis in simulation coordinates, which orients `ZonalHarmonics`.
+/// Bodies without one spin around +Z.
+#[derive(Debug, Clone, Copy)]
+pub struct SpinAxis(pub DVec3);
+
+impl Default for SpinAxis {
+    fn default() -> Self {
+        Self(DVec3::Z)
+    }
+}
+
+/// Mean radius, the surface that `Atmosphere` altitudes are measured from.
+#[derive(Debug, Clone, Copy)]
+pub struct Radius(pub Meters);
+
+impl Radius {
+    /// The radius, unless it is zero as for bodies defined without one.
+    pub fn surface(&self) -> Option<Meters> {
+        (self.0 > Meters(0.0)).then_some(self.0)
+    }
+}
+
+/// Exponential atmosphere, `density = surface_density * exp(-altitude / scale_height)`.
+/// Densities are in kg/m^3.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Atmosphere {
+    pub scale_height: Meters,
+    pub surface_density: f64,
+}
+
+/// Marks a body that emits light, such as the sun, with its luminosity in watts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LightSource {
+    pub luminosity: f64,
+}
+
+/// How a small body like a spacecraft or dust grain couples to light and atmospheres.
+/// `reflectivity` goes from 0 (absorbs everything) to 1 (mirror), `area_to_mass` is in m^2/kg.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SurfaceProperties {
+    pub area_to_mass: f64,
+    pub reflectivity: f64,
+    pub drag_coefficient: f64,
+}
+
+/// `SimulationCoordinates` put bodies in the floating origin's frame, like everything else in it.
 #[derive(Bundle)]
 pub struct BodyBundle {
     mass: Mass,
+    pos: Position,
     transform: Transform,
+    global_transform: GlobalTransform,
+    coordinates: SimulationCoordinates,
     vel: Velocity,
     acc: Acceleration,
 }
 
 impl BodyBundle {
-    pub fn new(mass: f32, pos: Vec3, vel: Vec3) -> Self {
+    pub fn new(mass: Kilograms, pos: Position, vel: Velocity) -> Self {
         Self {
             mass: Mass(mass),
-            transform: Transform::from_translation(pos),
-            vel: Velocity(vel),
+            pos,
+            transform: Transform::default(),
+            global_transform: GlobalTransform::default(),
+            coordinates: SimulationCoordinates::default(),
+            vel,
             acc: Acceleration::default(),
         }
     }
+
+    /// Circular orbit starting on the parent's +X side, tilted by `inclination` radians
+    /// around the X axis. The body is massless until `with_mass` is called.
+    pub fn circular_orbit_around(parent: &ParentBody, radius: Meters, inclination: f64) -> Self {
+        let speed = (parent.gravitational_parameter() / radius.0).sqrt();
+        let (sin, cos) = inclination.sin_cos();
+        Self::new(
+            Kilograms(0.0),
+            parent.position + Position::new(radius.0, 0.0, 0.0),
+            parent.velocity + Velocity::new(0.0, cos, sin) * speed,
+        )
+    }
+
+    /// Prograde ecliptic orbit starting at periapsis on the parent's +X side.
+    pub fn elliptic(parent: &ParentBody, periapsis: Meters, apoapsis: Meters) -> Self {
+        let semi_major_axis = (periapsis + apoapsis) * 0.5;
+        let speed = (parent.gravitational_parameter()
+            * (2.0 / periapsis.0 - 1.0 / semi_major_axis.0))
+            .sqrt();
+        Self::new(
+            Kilograms(0.0),
+            parent.position + Position::new(periapsis.0, 0.0, 0.0),
+            parent.velocity + Velocity::new(0.0, speed, 0.0),
+        )
+    }
+
+    /// Hyperbolic orbit with its periapsis on the parent's surface, so the body leaves with
+    /// `v_inf` once it is far away. `None` if the parent has no `Radius` to start from.
+    pub fn escape_trajectory(parent: &ParentBody, v_inf: MetersPerSecond) -> Option<Self> {
+        let radius = parent.radius?.0;
+        let speed = (v_inf.0 * v_inf.0 + 2.0 * parent.gravitational_parameter() / radius).sqrt();
+        Some(Self::new(
+            Kilograms(0.0),
+            parent.position + Position::new(radius, 0.0, 0.0),
+            parent.velocity + Velocity::new(0.0, speed, 0.0),
+        ))
+    }
+
+    pub fn with_mass(mut self, mass: Kilograms) -> Self {
+        self.mass = Mass(mass);
+        self
+    }
+}
+
+/// Mass and state of a body that new bodies are placed around, read from the world.
+#[derive(Debug, Clone, Copy)]
+pub struct ParentBody {
+    pub mass: Kilograms,
+    pub position: Position,
+    pub velocity: Velocity,
+    /// `None` if the body has no `Radius` or a zero one
+    pub radius: Option<Meters>,
+}
+
+impl ParentBody {
+    pub fn from_world(world: &World, entity: Entity) -> Option<Self> {
+        Some(Self {
+            mass: world.get::<Mass>(entity)?.0,
+            position: *world.get::<Position>(entity)?,
+            velocity: *world.get::<Velocity>(entity)?,
+            radius: world.get::<Radius>(entity).and_then(Radius::surface),
+        })
+    }
+
+    pub fn from_query(
+        bodies: &Query<(&Mass, &Position, &Velocity, Option<&Radius>)>,
+        entity: Entity,
+    ) -> Option<Self> {
+        let (mass, pos, vel, radius) = bodies.get(entity).ok()?;
+        Some(Self {
+            mass: mass.0,
+            position: *pos,
+            velocity: *vel,
+            radius: radius.and_then(Radius::surface),
+        })
+    }
+
+    pub fn gravitational_parameter(&self) -> f64 {
+        G * self.mass.0
+    }
 }
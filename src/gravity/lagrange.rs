@@ -0,0 +1,216 @@
+use crate::gravity::{body::*, nbody::*};
+use crate::scale::*;
+use crate::units::*;
+use bevy::{math::DVec3, prelude::*};
+use bevy_origin_rebasing::SimulationBundle;
+use bevy_prototype_debug_lines::DebugLines;
+
+pub const LAGRANGE_POINTS_SYSTEM: &str = "lagrange_points";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LagrangePoint {
+    L1,
+    L2,
+    L3,
+    L4,
+    L5,
+}
+
+impl LagrangePoint {
+    pub const ALL: [LagrangePoint; 5] = [
+        LagrangePoint::L1,
+        LagrangePoint::L2,
+        LagrangePoint::L3,
+        LagrangePoint::L4,
+        LagrangePoint::L5,
+    ];
+}
+
+/// The two bodies a `LagrangePoint` entity follows, e.g. the sun and the earth.
+#[derive(Debug, Clone, Copy)]
+pub struct LagrangePair {
+    pub primary: Entity,
+    pub secondary: Entity,
+}
+
+impl LagrangePair {
+    pub fn new(primary: Entity, secondary: Entity) -> Self {
+        Self { primary, secondary }
+    }
+
+    /// Current position and velocity of `point`, or `None` if either body is missing.
+    pub fn state(
+        &self,
+        point: LagrangePoint,
+        bodies: &Query<(&Mass, &Position, &Velocity)>,
+    ) -> Option<(Position, Velocity)> {
+        let (m1, p1, v1) = bodies.get(self.primary).ok()?;
+        let (m2, p2, v2) = bodies.get(self.secondary).ok()?;
+        Some(lagrange_state((m1.0, *p1, *v1), (m2.0, *p2, *v2), point))
+    }
+
+    /// A body sitting at `point` and co-moving with the pair. Only L4 and L5 are stable,
+    /// anything placed at L1-L3 drifts away within a few orbits.
+    pub fn body_at(
+        &self,
+        point: LagrangePoint,
+        mass: Kilograms,
+        bodies: &Query<(&Mass, &Position, &Velocity)>,
+    ) -> Option<BodyBundle> {
+        let (pos, vel) = self.state(point, bodies)?;
+        Some(BodyBundle::new(mass, pos, vel))
+    }
+}
+
+/// Shows a cross at every Lagrange point.
+pub struct LagrangeMarkers {
+    pub visible: bool,
+    pub size: Meters,
+}
+
+impl Default for LagrangeMarkers {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            size: AstronomicalUnits(0.005).into(),
+        }
+    }
+}
+
+pub struct LagrangePlugin;
+
+impl Plugin for LagrangePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<LagrangeMarkers>()
+            .init_resource::<WorldScale>()
+            .add_startup_system_to_stage(StartupStage::PostStartup, update_lagrange_points.system())
+            .add_system(
+                update_lagrange_points
+                    .system()
+                    .label(LAGRANGE_POINTS_SYSTEM)
+                    .after(FINISH_STEP_SYSTEM)
+                    .before(SYNC_TRANSFORMS_SYSTEM),
+            )
+            .add_system(draw_lagrange_markers.system().after(SYNC_TRANSFORMS_SYSTEM));
+    }
+}
+
+/// Spawns the five Lagrange points of `pair`, named like "sun-earth L4". They have a
+/// `Position` and `Velocity` but no `Mass`, so the integrator leaves them alone.
+pub fn spawn_lagrange_points(
+    commands: &mut Commands,
+    name: &str,
+    pair: LagrangePair,
+) -> Vec<Entity> {
+    LagrangePoint::ALL
+        .iter()
+        .map(|point| {
+            commands
+                .spawn_bundle(SimulationBundle::default())
+                .insert(Position::default())
+                .insert(Velocity::default())
+                .insert(pair)
+                .insert(*point)
+                .insert(Name::new(format!("{} {:?}", name, point)))
+                .id()
+        })
+        .collect()
+}
+
+// Positions are found in the frame rotating and pulsating with the pair, where the points are
+// fixed, and mapped back to inertial coordinates. The collinear points solve
+// x = (1 - mu)(x + mu) / |x + mu|^3 + mu (x - 1 + mu) / |x - 1 + mu|^3
+// for separation 1 and the barycentre at the origin, which Newton's method does in a few steps.
+pub fn lagrange_state(
+    primary: (Kilograms, Position, Velocity),
+    secondary: (Kilograms, Position, Velocity),
+    point: LagrangePoint,
+) -> (Position, Velocity) {
+    let (Kilograms(m1), Position(p1), Velocity(v1)) = primary;
+    let (Kilograms(m2), Position(p2), Velocity(v2)) = secondary;
+    let total = m1 + m2;
+    let mu = m2 / total;
+    let barycentre = (p1 * m1 + p2 * m2) / total;
+    let barycentre_velocity = (v1 * m1 + v2 * m2) / total;
+
+    let r = p2 - p1;
+    let v = v2 - v1;
+    let separation = r.length();
+    let angular_momentum = r.cross(v);
+    let x_axis = r / separation;
+    let normal = if angular_momentum.length_squared() > 0.0 {
+        angular_momentum.normalize()
+    } else if x_axis.z.abs() < 0.9 {
+        x_axis.cross(DVec3::Z).normalize()
+    } else {
+        x_axis.cross(DVec3::X).normalize()
+    };
+    let y_axis = normal.cross(x_axis);
+    let angular_velocity = angular_momentum / (separation * separation);
+    let expansion_rate = r.dot(v) / (separation * separation);
+
+    let (x, y) = match point {
+        LagrangePoint::L1 => (collinear_point(mu, 1.0 - mu - hill_radius(mu)), 0.0),
+        LagrangePoint::L2 => (collinear_point(mu, 1.0 - mu + hill_radius(mu)), 0.0),
+        LagrangePoint::L3 => (collinear_point(mu, -1.0 - 5.0 * mu / 12.0), 0.0),
+        LagrangePoint::L4 => (0.5 - mu, 3f64.sqrt() / 2.0),
+        LagrangePoint::L5 => (0.5 - mu, -(3f64.sqrt()) / 2.0),
+    };
+
+    let offset = (x_axis * x + y_axis * y) * separation;
+    let velocity = barycentre_velocity + angular_velocity.cross(offset) + offset * expansion_rate;
+    (Position(barycentre + offset), Velocity(velocity))
+}
+
+fn hill_radius(mu: f64) -> f64 {
+    (mu / 3.0).cbrt()
+}
+
+fn collinear_point(mu: f64, guess: f64) -> f64 {
+    let mut x = guess;
+    for _ in 0..50 {
+        let d1 = x + mu;
+        let d2 = x - 1.0 + mu;
+        let (a1, a2) = (d1.abs().powi(3), d2.abs().powi(3));
+        let f = x - (1.0 - mu) * d1 / a1 - mu * d2 / a2;
+        let df = 1.0 + 2.0 * (1.0 - mu) / a1 + 2.0 * mu / a2;
+        let step = f / df;
+        x -= step;
+        if step.abs() < 1e-15 {
+            break;
+        }
+    }
+    x
+}
+
+fn update_lagrange_points(
+    bodies: Query<(&Mass, &Position, &Velocity)>,
+    mut points: Query<(&LagrangePair, &LagrangePoint, &mut Position, &mut Velocity), Without<Mass>>,
+) {
+    for (pair, point, mut pos, mut vel) in points.iter_mut() {
+        if let Some((point_pos, point_vel)) = pair.state(*point, &bodies) {
+            *pos = point_pos;
+            *vel = point_vel;
+        }
+    }
+}
+
+fn draw_lagrange_markers(
+    markers: Res<LagrangeMarkers>,
+    scale: Res<WorldScale>,
+    lines: Option<ResMut<DebugLines>>,
+    points: Query<&Transform, With<LagrangePoint>>,
+) {
+    let mut lines = match lines {
+        Some(lines) if markers.visible => lines,
+        _ => return,
+    };
+    for transform in points.iter() {
+        let centre = transform.translation;
+        let size = scale.to_render_units(markers.size);
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z].iter() {
+            let arm = *axis * size;
+            lines.line_colored(centre - arm, centre + arm, 0.0, Color::YELLOW);
+        }
+    }
+}
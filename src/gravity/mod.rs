@@ -1,3 +1,6 @@
 pub mod body;
+pub mod lagrange;
 pub mod nbody;
+pub mod perturbations;
 pub mod resources;
+pub mod system;
@@ -1 +1,235 @@
+use crate::gravity::{body::*, perturbations::*, resources::*};
+use crate::scale::WorldScale;
+use crate::units::Kilograms;
+use bevy::prelude::*;
+use bevy_origin_rebasing::{
+    FloatingOrigin, GridCell, OriginRebasingSettings, SimulationCoordinates, TELEPORT_SYSTEM,
+};
 
+pub const BEGIN_STEP_SYSTEM: &str = "nbody_begin_step";
+pub const FORCES: &str = "nbody_forces";
+pub const FINISH_STEP_SYSTEM: &str = "nbody_finish_step";
+pub const SYNC_TRANSFORMS_SYSTEM: &str = "nbody_sync_transforms";
+pub const TELEPORT_BODIES_SYSTEM: &str = "nbody_teleport_bodies";
+
+pub struct NBodyPlugin;
+
+impl Plugin for NBodyPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<Gravity>()
+            .init_resource::<PostNewtonian>()
+            .init_resource::<SimulationSettings>()
+            .init_resource::<SimulationClock>()
+            .init_resource::<WorldScale>()
+            .add_system(
+                teleport_bodies
+                    .system()
+                    .label(TELEPORT_BODIES_SYSTEM)
+                    .before(TELEPORT_SYSTEM)
+                    .before(BEGIN_STEP_SYSTEM),
+            )
+            .add_system(
+                begin_step
+                    .system()
+                    .label(BEGIN_STEP_SYSTEM)
+                    .after(TELEPORT_BODIES_SYSTEM),
+            )
+            .add_system(
+                newtonian_gravity
+                    .system()
+                    .label(FORCES)
+                    .after(BEGIN_STEP_SYSTEM),
+            )
+            .add_system(
+                post_newtonian_correction
+                    .system()
+                    .label(FORCES)
+                    .after(BEGIN_STEP_SYSTEM),
+            )
+            .add_system(
+                zonal_harmonics
+                    .system()
+                    .label(FORCES)
+                    .after(BEGIN_STEP_SYSTEM),
+            )
+            .add_system(
+                radiation_pressure
+                    .system()
+                    .label(FORCES)
+                    .after(BEGIN_STEP_SYSTEM),
+            )
+            .add_system(
+                atmospheric_drag
+                    .system()
+                    .label(FORCES)
+                    .after(BEGIN_STEP_SYSTEM),
+            )
+            .add_system(finish_step.system().label(FINISH_STEP_SYSTEM).after(FORCES))
+            .add_system(
+                sync_transforms
+                    .system()
+                    .label(SYNC_TRANSFORMS_SYSTEM)
+                    .after(FINISH_STEP_SYSTEM),
+            );
+    }
+}
+
+// `sync_transforms` rebuilds `SimulationCoordinates` from `Position`, so a teleport has to move
+// `Position` as well. The teleport itself stays pending for the rebasing plugin to apply.
+fn teleport_bodies(
+    scale: Res<WorldScale>,
+    mut query: Query<(&mut Position, &SimulationCoordinates), Changed<SimulationCoordinates>>,
+) {
+    for (mut pos, coordinates) in query.iter_mut() {
+        if let Some(translation) = coordinates.pending_teleport() {
+            pos.0 = translation / scale.0;
+        }
+    }
+}
+
+// Every integrator is split around a single force evaluation, so any system labelled
+// `FORCES` can add to `Acceleration` without knowing which integrator is in use.
+fn begin_step(
+    settings: Res<SimulationSettings>,
+    mut query: Query<(&mut Position, &Velocity, &mut Acceleration)>,
+) {
+    let dt = settings.timestep;
+    for (mut pos, vel, mut acc) in query.iter_mut() {
+        if settings.integrator == Integrator::Leapfrog {
+            *pos += *vel * dt * 0.5;
+        }
+        *acc = Acceleration::ZERO;
+    }
+}
+
+pub fn newtonian_gravity(
+    gravity: Res<Gravity>,
+    attractors: Query<(Entity, &Mass, &Position)>,
+    mut bodies: Query<(Entity, &Position, &mut Acceleration)>,
+) {
+    let attractors: Vec<_> = attractors
+        .iter()
+        .filter(|(_, mass, _)| mass.0 > Kilograms(0.0))
+        .map(|(entity, &Mass(mass), pos)| (entity, mass, *pos))
+        .collect();
+
+    for (entity, pos, mut acc) in bodies.iter_mut() {
+        for (other, mass, other_pos) in attractors.iter() {
+            if *other == entity {
+                continue;
+            }
+            let r = (*other_pos - *pos).0;
+            let distance_squared = r.length_squared();
+            acc.0 += r * (gravity.0 * mass.0 / (distance_squared * distance_squared.sqrt()));
+        }
+    }
+}
+
+// Pairwise Schwarzschild term, which is exact for a test body around a dominant mass:
+// a = GM / (c^2 r^3) * ((4GM / r - v^2) r + 4 (r . v) v)
+pub fn post_newtonian_correction(
+    gravity: Res<Gravity>,
+    post_newtonian: Res<PostNewtonian>,
+    attractors: Query<(Entity, &Mass, &Position, &Velocity)>,
+    mut bodies: Query<(Entity, &Position, &Velocity, &mut Acceleration)>,
+) {
+    if !post_newtonian.0 {
+        return;
+    }
+
+    let attractors: Vec<_> = attractors
+        .iter()
+        .filter(|(_, mass, ..)| mass.0 > Kilograms(0.0))
+        .map(|(entity, &Mass(mass), pos, vel)| (entity, gravity.0 * mass.0, *pos, *vel))
+        .collect();
+
+    let c_squared = SPEED_OF_LIGHT * SPEED_OF_LIGHT;
+    for (entity, pos, vel, mut acc) in bodies.iter_mut() {
+        for (other, mu, other_pos, other_vel) in attractors.iter() {
+            if *other == entity {
+                continue;
+            }
+            let r = (*pos - *other_pos).0;
+            let v = (*vel - *other_vel).0;
+            let distance = r.length();
+            acc.0 += (r * (4.0 * mu / distance - v.length_squared()) + v * (4.0 * r.dot(v)))
+                * (mu / (c_squared * distance.powi(3)));
+        }
+    }
+}
+
+fn finish_step(
+    settings: Res<SimulationSettings>,
+    mut clock: ResMut<SimulationClock>,
+    mut query: Query<(&mut Position, &mut Velocity, &Acceleration)>,
+) {
+    let dt = settings.timestep;
+    for (mut pos, mut vel, acc) in query.iter_mut() {
+        match settings.integrator {
+            Integrator::Euler => {
+                *pos += *vel * dt;
+                *vel += *acc * dt;
+            }
+            Integrator::SemiImplicitEuler => {
+                *vel += *acc * dt;
+                *pos += *vel * dt;
+            }
+            Integrator::Leapfrog => {
+                *vel += *acc * dt;
+                *pos += *vel * dt * 0.5;
+            }
+        }
+    }
+    clock.elapsed += dt.0;
+    clock.steps += 1;
+}
+
+/// Bodies with `SimulationCoordinates` get their cell and offset from `Position` and are placed
+/// relative to the floating origin's cell, everything else at its absolute scaled position.
+#[allow(clippy::type_complexity)]
+fn sync_transforms(
+    scale: Res<WorldScale>,
+    rebasing: Option<Res<OriginRebasingSettings>>,
+    origin: Option<Res<FloatingOrigin>>,
+    mut query: QuerySet<(
+        Query<
+            (
+                &Position,
+                &mut Transform,
+                Option<&mut SimulationCoordinates>,
+            ),
+            Changed<Position>,
+        >,
+        Query<(
+            &Position,
+            &mut Transform,
+            Option<&mut SimulationCoordinates>,
+        )>,
+    )>,
+) {
+    let cell_size = rebasing.map(|rebasing| rebasing.render_cell_size(&scale));
+    let origin = SimulationCoordinates::new(
+        origin.map_or(GridCell::ZERO, |origin| origin.cell),
+        Vec3::ZERO,
+    );
+    let place = |pos: &Position,
+                 mut transform: Mut<Transform>,
+                 coordinates: Option<Mut<SimulationCoordinates>>| {
+        match (coordinates, cell_size) {
+            (Some(mut coordinates), Some(cell_size)) => {
+                *coordinates = SimulationCoordinates::from_translation(pos.0 * scale.0, cell_size);
+                transform.translation = coordinates.relative_translation(&origin, cell_size);
+            }
+            _ => transform.translation = scale.position(pos.0),
+        }
+    };
+    if scale.is_changed() {
+        for (pos, transform, coordinates) in query.q1_mut().iter_mut() {
+            place(pos, transform, coordinates);
+        }
+    } else {
+        for (pos, transform, coordinates) in query.q0_mut().iter_mut() {
+            place(pos, transform, coordinates);
+        }
+    }
+}
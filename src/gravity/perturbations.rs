@@ -0,0 +1,98 @@
+use crate::gravity::{body::*, resources::*};
+use bevy::prelude::*;
+use std::f64::consts::PI;
+
+// Gradient of the J2 and J3 terms of the body's potential, with z measured along the spin axis:
+// a2 = -3/2 J2 mu R^2 / r^5 * ((1 - 5 z^2 / r^2) r + 2 z k)
+// a3 = -1/2 J3 mu R^3 / r^5 * (5 z (3 - 7 z^2 / r^2) / r^2 r + 3 (5 z^2 / r^2 - 1) k)
+// The reaction on the oblate body itself is left out, it is negligible for satellites.
+pub fn zonal_harmonics(
+    gravity: Res<Gravity>,
+    oblate_bodies: Query<(Entity, &Mass, &Position, &ZonalHarmonics, Option<&SpinAxis>)>,
+    mut bodies: Query<(Entity, &Position, &mut Acceleration)>,
+) {
+    let oblate_bodies: Vec<_> = oblate_bodies
+        .iter()
+        .map(|(entity, &Mass(mass), pos, harmonics, spin_axis)| {
+            let axis = spin_axis.copied().unwrap_or_default().0.normalize();
+            (entity, gravity.0 * mass.0, *pos, *harmonics, axis)
+        })
+        .collect();
+
+    for (entity, pos, mut acc) in bodies.iter_mut() {
+        for (other, mu, other_pos, harmonics, axis) in oblate_bodies.iter() {
+            if *other == entity {
+                continue;
+            }
+            let r = (*pos - *other_pos).0;
+            let distance_squared = r.length_squared();
+            let z = r.dot(*axis);
+            let z2_r2 = z * z / distance_squared;
+            let mu_r5 = mu / (distance_squared * distance_squared * distance_squared.sqrt());
+            let radius = harmonics.reference_radius.0;
+
+            acc.0 -= (r * (1.0 - 5.0 * z2_r2) + *axis * (2.0 * z))
+                * (1.5 * harmonics.j2 * mu_r5 * radius * radius);
+            acc.0 -= (r * (5.0 * z * (3.0 - 7.0 * z2_r2) / distance_squared)
+                + *axis * (3.0 * (5.0 * z2_r2 - 1.0)))
+                * (0.5 * harmonics.j3 * mu_r5 * radius * radius * radius);
+        }
+    }
+}
+
+// Cannonball model without eclipses: a = (1 + reflectivity) * L / (4 pi c d^2) * A/m, pointing away
+// from the light source.
+pub fn radiation_pressure(
+    light_sources: Query<(Entity, &Position, &LightSource)>,
+    mut bodies: Query<(Entity, &Position, &SurfaceProperties, &mut Acceleration)>,
+) {
+    let light_sources: Vec<_> = light_sources
+        .iter()
+        .map(|(entity, pos, light)| (entity, *pos, light.luminosity))
+        .collect();
+
+    for (entity, pos, surface, mut acc) in bodies.iter_mut() {
+        for (source, source_pos, luminosity) in light_sources.iter() {
+            if *source == entity {
+                continue;
+            }
+            let r = (*pos - *source_pos).0;
+            let distance_squared = r.length_squared();
+            let pressure = luminosity / (4.0 * PI * SPEED_OF_LIGHT * distance_squared);
+            acc.0 +=
+                r.normalize() * ((1.0 + surface.reflectivity) * pressure * surface.area_to_mass);
+        }
+    }
+}
+
+// a = -1/2 rho Cd A/m |v| v, with v relative to the body the atmosphere belongs to.
+// The atmosphere does not co-rotate with its body.
+pub fn atmospheric_drag(
+    atmospheres: Query<(Entity, &Position, &Velocity, &Radius, &Atmosphere)>,
+    mut bodies: Query<(
+        Entity,
+        &Position,
+        &Velocity,
+        &SurfaceProperties,
+        &mut Acceleration,
+    )>,
+) {
+    let atmospheres: Vec<_> = atmospheres
+        .iter()
+        .map(|(entity, pos, vel, radius, atmosphere)| (entity, *pos, *vel, radius.0, *atmosphere))
+        .collect();
+
+    for (entity, pos, vel, surface, mut acc) in bodies.iter_mut() {
+        for (other, other_pos, other_vel, radius, atmosphere) in atmospheres.iter() {
+            if *other == entity {
+                continue;
+            }
+            let altitude = (pos.distance(*other_pos) - *radius).0.max(0.0);
+            let density =
+                atmosphere.surface_density * (-altitude / atmosphere.scale_height.0).exp();
+            let v = (*vel - *other_vel).0;
+            acc.0 -=
+                v * (0.5 * density * surface.drag_coefficient * surface.area_to_mass * v.length());
+        }
+    }
+}
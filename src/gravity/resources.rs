@@ -1,9 +1,72 @@
-pub const G: f32 = 6.67430e-11_f32;
+use crate::epoch::Epoch;
+use crate::units::Seconds;
+use std::str::FromStr;
 
-pub struct Gravity(pub f32);
+pub const G: f64 = 6.67430e-11;
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+pub struct Gravity(pub f64);
 
 impl Default for Gravity {
     fn default() -> Self {
         Self(G)
     }
 }
+
+/// Adds the first-order post-Newtonian (1PN) correction to gravity, which is what makes
+/// Mercury's perihelion precess by the extra ~43 arcsec per century.
+#[derive(Default)]
+pub struct PostNewtonian(pub bool);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrator {
+    /// Explicit Euler. First order and not symplectic, orbits slowly spiral outwards.
+    Euler,
+    /// Semi-implicit (symplectic) Euler. First order, but energy stays bounded.
+    SemiImplicitEuler,
+    /// Drift-kick-drift leapfrog. Second order and symplectic.
+    Leapfrog,
+}
+
+impl FromStr for Integrator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "euler" => Ok(Integrator::Euler),
+            "semi-implicit-euler" | "symplectic-euler" => Ok(Integrator::SemiImplicitEuler),
+            "leapfrog" | "verlet" => Ok(Integrator::Leapfrog),
+            _ => Err(format!("Unknown integrator '{}'", s)),
+        }
+    }
+}
+
+pub struct SimulationSettings {
+    /// Simulated time advanced per update.
+    pub timestep: Seconds,
+    pub integrator: Integrator,
+}
+
+impl Default for SimulationSettings {
+    fn default() -> Self {
+        Self {
+            timestep: Seconds(3_600.0),
+            integrator: Integrator::Leapfrog,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SimulationClock {
+    /// Epoch of the initial state vectors.
+    pub start: Epoch,
+    /// Simulated seconds since the system was loaded.
+    pub elapsed: f64,
+    pub steps: u64,
+}
+
+impl SimulationClock {
+    pub fn epoch(&self) -> Epoch {
+        self.start + self.elapsed
+    }
+}
@@ -0,0 +1,227 @@
+use crate::epoch::Epoch;
+use crate::gravity::body::*;
+use crate::units::*;
+use bevy::{ecs::system::EntityCommands, math::DVec3, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+const J2000_OBLIQUITY: f64 = 23.439_291_1;
+
+/// A set of bodies and their initial state vectors, in SI units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemDefinition {
+    pub name: String,
+    /// Epoch of the state vectors, J2000 if omitted.
+    #[serde(default)]
+    pub epoch: Epoch,
+    /// Whether this system needs the 1PN correction, see `PostNewtonian`.
+    #[serde(default)]
+    pub post_newtonian: bool,
+    pub bodies: Vec<BodyDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyDefinition {
+    pub name: String,
+    pub mass: Kilograms,
+    pub position: Position,
+    pub velocity: Velocity,
+    #[serde(default)]
+    pub radius: Meters,
+    #[serde(default)]
+    pub zonal_harmonics: Option<ZonalHarmonics>,
+    /// Unit vector, +Z if omitted
+    #[serde(default)]
+    pub spin_axis: Option<DVec3>,
+    #[serde(default)]
+    pub light_source: Option<LightSource>,
+    #[serde(default)]
+    pub atmosphere: Option<Atmosphere>,
+    #[serde(default)]
+    pub surface: Option<SurfaceProperties>,
+}
+
+impl BodyDefinition {
+    pub fn bundle(&self) -> BodyBundle {
+        BodyBundle::new(self.mass, self.position, self.velocity)
+    }
+
+    /// Inserts the body's simulation components and name into an existing entity.
+    pub fn insert_into(&self, entity: &mut EntityCommands) {
+        entity.insert_bundle(self.bundle());
+        self.insert_properties_into(entity);
+    }
+
+    /// Inserts everything but the state and `Transform`, and removes the optional components
+    /// this body doesn't have, so an existing body can be brought in line with the definition.
+    pub fn insert_properties_into(&self, entity: &mut EntityCommands) {
+        entity
+            .insert(Radius(self.radius))
+            .insert(Name::new(self.name.clone()));
+        match self.zonal_harmonics {
+            Some(harmonics) => entity.insert(harmonics),
+            None => entity.remove::<ZonalHarmonics>(),
+        };
+        match self.spin_axis {
+            Some(axis) => entity.insert(SpinAxis(axis.normalize())),
+            None => entity.remove::<SpinAxis>(),
+        };
+        match self.light_source {
+            Some(light_source) => entity.insert(light_source),
+            None => entity.remove::<LightSource>(),
+        };
+        match self.atmosphere {
+            Some(atmosphere) => entity.insert(atmosphere),
+            None => entity.remove::<Atmosphere>(),
+        };
+        match self.surface {
+            Some(surface) => entity.insert(surface),
+            None => entity.remove::<SurfaceProperties>(),
+        };
+    }
+}
+
+/// Converts a rotation pole given as ICRF right ascension and declination, in degrees,
+/// to the ecliptic frame the JPL state vectors are in.
+pub fn spin_axis_from_pole(right_ascension: f64, declination: f64) -> DVec3 {
+    let (ra, dec) = (right_ascension.to_radians(), declination.to_radians());
+    let equatorial = DVec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let (sin, cos) = J2000_OBLIQUITY.to_radians().sin_cos();
+    DVec3::new(
+        equatorial.x,
+        cos * equatorial.y + sin * equatorial.z,
+        -sin * equatorial.y + cos * equatorial.z,
+    )
+}
+
+impl SystemDefinition {
+    /// Reads a system definition from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn body(&self, name: &str) -> Option<&BodyDefinition> {
+        self.bodies.iter().find(|body| body.name == name)
+    }
+
+    /// Spawns every body without any rendering components, for headless use.
+    pub fn spawn(&self, commands: &mut Commands) -> Vec<Entity> {
+        self.bodies
+            .iter()
+            .map(|body| {
+                let mut entity = commands.spawn();
+                body.insert_into(&mut entity);
+                entity.id()
+            })
+            .collect()
+    }
+
+    // The ephemeris is copied as published, digits past f64 precision included
+    #[allow(clippy::excessive_precision)]
+    pub fn solar_system() -> Self {
+        // Masses in 10^24 kg, positions in AU, velocities in AU/day and radii in km
+        let jpl =
+            |name: &str, mass: f64, pos: [f64; 3], vel: [f64; 3], radius: f64| BodyDefinition {
+                name: name.to_string(),
+                mass: Kilograms(mass * 1e24),
+                position: Position(DVec3::from(pos) * METERS_PER_ASTRONOMICAL_UNIT),
+                velocity: Velocity(
+                    DVec3::from(vel) * (METERS_PER_ASTRONOMICAL_UNIT / SECONDS_PER_DAY),
+                ),
+                radius: Kilometers(radius).into(),
+                zonal_harmonics: None,
+                spin_axis: None,
+                light_source: None,
+                atmosphere: None,
+                surface: None,
+            };
+
+        // Data pulled from JPL Horizons as of 2021-04-18 00:00 TDB
+        // https://ssd.jpl.nasa.gov/horizons.cgi
+        #[rustfmt::skip]
+        let mut bodies = vec![
+            jpl("sun", 1_988_500.0, [0.0, 0.0, 0.0], [0.0, 0.0, 0.0], 695_508.0),
+            jpl("mercury", 0.3302,
+                [3.044170697902298E-01, 1.295114876282963E-01, -1.734104195212369E-02],
+                [-1.648628006573339E-02, 2.713585294570181E-02, 3.729745700066048E-03],
+                2439.7),
+            jpl("venus", 4.868,
+                [5.387247476293335E-01, 4.820230339302334E-01, -2.447215630265642E-02],
+                [-1.354845714410186E-02, 1.498631588335955E-02, 9.874886299710420E-04],
+                6051.84),
+            jpl("earth", 5.97219,
+                [-8.873674344461769E-01, -4.697992257377307E-01, 2.381003809013169E-05],
+                [7.775921491692710E-03, -1.526923260035268E-02, 1.329236295796724E-07],
+                6371.01),
+            jpl("mars", 0.64171,
+                [-7.669365607923907E-01, 1.437715683938847E+00, 4.894216325150345E-02],
+                [-1.181841087219943E-02, -5.396860897762226E-03, 1.768153357356463E-04],
+                3389.92),
+            jpl("jupiter", 1898.187,
+                [3.638338491378654E+00, -3.517196054099748E+00, -6.679350348303023E-02],
+                [5.159638546395391E-03, 5.787459942412818E-03, -1.394560955359292E-04],
+                69911.0),
+            jpl("saturn", 568.34,
+                [5.946821461107053E+00, -8.000786524501104E+00, -9.757186586148088E-02],
+                [4.173453543382942E-03, 3.320093983241896E-03, -2.235785645393874E-04],
+                58232.0),
+            jpl("uranus", 86.813,
+                [1.507889019392361E+01, 1.276651492152234E+01, -1.479475386482554E-01],
+                [-2.565701401124483E-03, 2.824133197172000E-03, 4.363663945419187E-05],
+                25362.0),
+            jpl("neptune", 102.4126,
+                [2.951580077181258E+01, -4.898113153026739E+00, -5.794227616270428E-01],
+                [4.988324362083494E-04, 3.122660147661985E-03, -7.542919141146281E-05],
+                24622.0),
+            jpl("pluto", 0.013030,
+                [1.437474170944128E+01, -3.109027718169479E+01, -8.297576366914019E-01],
+                [2.929346098298212E-03, 6.560315763737425E-04, -9.025427350060328E-04],
+                11880.3),
+        ];
+
+        // Pole orientations from the IAU WGCCRE report, harmonics from JGM-3 and Juno
+        for body in bodies.iter_mut() {
+            let (harmonics, pole) = match body.name.as_str() {
+                "earth" => ((1.082_626_68e-3, -2.532_656e-6, 6_378_137.0), (0.0, 90.0)),
+                "jupiter" => (
+                    (1.469_656_4e-2, -4.2e-8, 71_492_000.0),
+                    (268.056_595, 64.495_303),
+                ),
+                _ => continue,
+            };
+            body.zonal_harmonics = Some(ZonalHarmonics {
+                j2: harmonics.0,
+                j3: harmonics.1,
+                reference_radius: Meters(harmonics.2),
+            });
+            body.spin_axis = Some(spin_axis_from_pole(pole.0, pole.1));
+        }
+
+        for body in bodies.iter_mut() {
+            match body.name.as_str() {
+                "sun" => {
+                    body.light_source = Some(LightSource {
+                        luminosity: 3.828e26,
+                    })
+                }
+                "earth" => {
+                    body.atmosphere = Some(Atmosphere {
+                        scale_height: Meters(8_500.0),
+                        surface_density: 1.225,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            name: "solar system".to_string(),
+            epoch: "2021-04-18T00:00:00 TDB".parse().unwrap(),
+            post_newtonian: false,
+            bodies,
+        }
+    }
+}
@@ -1,9 +1,13 @@
 pub mod cameras;
 pub mod controllers;
+pub mod epoch;
 pub mod gravity;
 pub mod spawn;
 
 pub mod log;
+pub mod snapshot;
+pub mod trajectory;
+pub mod units;
 pub mod utils;
 
 pub mod look;
@@ -5,13 +5,18 @@ use bevy::pbr::AmbientLight;
 use bevy::prelude::*;
 use bevy::render::wireframe::WireframePlugin;
 use bevy::wgpu::{WgpuFeature, WgpuFeatures, WgpuOptions};
-use bevy_dynamic_billboarding::DynamicBillboardingPlugin;
+use bevy_dynamic_billboarding::{DepthSettings, DynamicBillboardingPlugin};
 use bevy_dynamic_object_scaling::DynamicObjectScalingPlugin;
-use bevy_inspector_egui::{InspectableRegistry, WorldInspectorPlugin};
-use bevy_origin_rebasing::{OriginRebasingPlugin, SimulationBundle, SimulationCoordinates};
+use bevy_inspector_egui::{InspectableRegistry, InspectorPlugin, WorldInspectorPlugin};
+use bevy_origin_rebasing::{
+    OriginRebasingPlugin, PrecisionDiagnosticsPlugin, PrecisionHistogram, SimulationBundle,
+    SimulationCoordinates, TranslationPrecision,
+};
 use space::cameras::third_person::*;
 use space::controllers::character::*;
-use space::gravity::resources::Gravity;
+use space::gravity::lagrange::LagrangePlugin;
+use space::gravity::nbody::NBodyPlugin;
+use space::snapshot::SnapshotPlugin;
 use space::spawn::planets::*;
 use space::utils::*;
 
@@ -33,17 +38,29 @@ fn main() {
             },
             ..Default::default()
         })
-        .insert_resource(InspectableRegistry::default().with::<SimulationCoordinates>())
-        .init_resource::<Gravity>()
+        .insert_resource(
+            InspectableRegistry::default()
+                .with::<SimulationCoordinates>()
+                .with::<TranslationPrecision>(),
+        )
         .init_resource::<CharacterSettings>()
+        .insert_resource(world_scale_from_env())
         .insert_resource(ClearColor(Color::BLACK))
+        .insert_resource(DepthSettings {
+            logarithmic: true,
+            ..Default::default()
+        })
         // .insert_resource(Msaa { samples: 4 })
         .add_plugins(DefaultPlugins)
         .add_plugin(CharacterControllerPlugin)
         .add_plugin(ThirdPersonCameraPlugin)
+        .add_plugin(NBodyPlugin)
+        .add_plugin(LagrangePlugin)
+        .add_plugin(SnapshotPlugin)
         // .add_plugin(LogDiagnosticsPlugin::default())
         // .add_plugin(FrameTimeDiagnosticsPlugin::default())
         .add_system(exit_on_esc_system.system())
+        .add_system(show_epoch_in_title.system())
         // .add_startup_system(spawn_lights.system())
         .add_startup_system(spawn_character.system())
         // .add_startup_system(spawn_earth.system())
@@ -55,7 +72,9 @@ fn main() {
         .add_plugin(WorldInspectorPlugin::new())
         .add_plugin(DynamicObjectScalingPlugin)
         .add_plugin(DynamicBillboardingPlugin)
-        .add_plugin(OriginRebasingPlugin)
+        .add_plugin(OriginRebasingPlugin::default())
+        .add_plugin(PrecisionDiagnosticsPlugin::default())
+        .add_plugin(InspectorPlugin::<PrecisionHistogram>::new())
         .add_plugin(WireframePlugin)
         .run();
 }
@@ -1,17 +1,19 @@
-use bevy::math::Vec3;
+use crate::units::*;
+pub use bevy_world_scale::WorldScale;
 
-pub const AU_TO_UNIT_SCALE: f32 = 149_597_870_691.0 * M_TO_UNIT_SCALE;
-pub const KM_TO_UNIT_SCALE: f32 = 1_000.0 * M_TO_UNIT_SCALE;
-// pub const AU_TO_UNIT_SCALE: f32 = 200.0;
-// pub const KM_TO_UNIT_SCALE: f32 = 0.0001;
-pub const M_TO_UNIT_SCALE: f32 = 1.0;
+/// Unit-aware lengths on top of `WorldScale`, e.g. `scale.to_render_units(Kilometers(6051.84))`.
+pub trait ToRenderUnits {
+    fn to_render_units(&self, length: impl Into<Meters>) -> f32;
+}
 
-pub fn convert_metres_to_units(input: Vec3) -> Vec3 {
-    return input * M_TO_UNIT_SCALE;
+impl ToRenderUnits for WorldScale {
+    fn to_render_units(&self, length: impl Into<Meters>) -> f32 {
+        self.length(length.into().0)
+    }
 }
 
-pub fn print_scales() {
-    println!("1m = {}", M_TO_UNIT_SCALE);
-    println!("1km = {}", KM_TO_UNIT_SCALE);
-    println!("1au = {}", AU_TO_UNIT_SCALE);
+pub fn print_scales(scale: &WorldScale) {
+    println!("1m = {}", scale.to_render_units(Meters(1.0)));
+    println!("1km = {}", scale.to_render_units(Kilometers(1.0)));
+    println!("1au = {}", scale.to_render_units(AstronomicalUnits(1.0)));
 }
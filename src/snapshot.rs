@@ -0,0 +1,330 @@
+use crate::cameras::event::*;
+use crate::epoch::Epoch;
+use crate::gravity::{body::*, resources::SimulationClock, system::BodyDefinition};
+use crate::look::MouseSettings;
+use crate::scale::WorldScale;
+use crate::spawn::planets::spawn_body;
+use crate::units::Meters;
+use bevy::prelude::*;
+use bevy_origin_rebasing::{
+    FloatingOrigin, GridCell, OrginRebasingEvent, OriginRebasingSettings, Rebased,
+    SimulationCoordinates,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+pub const SNAPSHOT_VERSION: u32 = 1;
+pub const QUICKSAVE_PATH: &str = "quicksave.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub epoch: Epoch,
+    /// Simulated seconds since the system was loaded.
+    pub elapsed: f64,
+    pub steps: u64,
+    pub bodies: Vec<BodySnapshot>,
+    pub player: Option<PlayerSnapshot>,
+    pub yaw_pitch_roll: Vec3,
+}
+
+/// A body's full definition at the time of the snapshot, so loading restores its perturbations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodySnapshot {
+    #[serde(flatten)]
+    pub definition: BodyDefinition,
+    pub rotation: Quat,
+}
+
+type SavedBody<'a> = (
+    (
+        &'a Name,
+        &'a Mass,
+        &'a Position,
+        &'a Velocity,
+        &'a Transform,
+    ),
+    Option<&'a Radius>,
+    Option<&'a ZonalHarmonics>,
+    Option<&'a SpinAxis>,
+    Option<&'a LightSource>,
+    Option<&'a Atmosphere>,
+    Option<&'a SurfaceProperties>,
+);
+
+fn body_snapshot(body: SavedBody) -> BodySnapshot {
+    let (state, radius, harmonics, spin_axis, light_source, atmosphere, surface) = body;
+    let (name, mass, pos, vel, transform) = state;
+    BodySnapshot {
+        definition: BodyDefinition {
+            name: name.as_str().to_string(),
+            mass: mass.0,
+            position: *pos,
+            velocity: *vel,
+            radius: radius.map_or(Meters(0.0), |radius| radius.0),
+            zonal_harmonics: harmonics.copied(),
+            spin_axis: spin_axis.map(|axis| axis.0),
+            light_source: light_source.copied(),
+            atmosphere: atmosphere.copied(),
+            surface: surface.copied(),
+        },
+        rotation: transform.rotation,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub cell: [i64; 3],
+    pub local_translation: Vec3,
+}
+
+impl From<&SimulationCoordinates> for PlayerSnapshot {
+    fn from(coordinates: &SimulationCoordinates) -> Self {
+        let cell = coordinates.cell();
+        Self {
+            cell: [cell.x, cell.y, cell.z],
+            local_translation: coordinates.local_translation(),
+        }
+    }
+}
+
+impl From<&PlayerSnapshot> for SimulationCoordinates {
+    fn from(player: &PlayerSnapshot) -> Self {
+        let [x, y, z] = player.cell;
+        SimulationCoordinates::new(GridCell::new(x, y, z), player.local_translation)
+    }
+}
+
+impl Snapshot {
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = File::create(path)?;
+        Ok(serde_json::to_writer_pretty(BufWriter::new(file), self)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let snapshot: Self = serde_json::from_reader(BufReader::new(file))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Unsupported snapshot version {} (expected {})",
+                    snapshot.version, SNAPSHOT_VERSION
+                ),
+            ));
+        }
+        Ok(snapshot)
+    }
+}
+
+#[derive(Debug)]
+pub struct SaveSnapshotEvent {
+    path: PathBuf,
+}
+
+impl SaveSnapshotEvent {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Deref for SaveSnapshotEvent {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+#[derive(Debug)]
+pub struct LoadSnapshotEvent {
+    path: PathBuf,
+}
+
+impl LoadSnapshotEvent {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Deref for LoadSnapshotEvent {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &Self::Target {
+        &self.path
+    }
+}
+
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<SaveSnapshotEvent>()
+            .add_event::<LoadSnapshotEvent>()
+            .add_system(handle_quicksave_input.system())
+            .add_system(save_snapshots.system())
+            .add_system(load_snapshots.system());
+    }
+}
+
+fn handle_quicksave_input(
+    keys: Res<Input<KeyCode>>,
+    mut save_events: EventWriter<SaveSnapshotEvent>,
+    mut load_events: EventWriter<LoadSnapshotEvent>,
+) {
+    if keys.just_pressed(KeyCode::F5) {
+        save_events.send(SaveSnapshotEvent::new(QUICKSAVE_PATH));
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        load_events.send(LoadSnapshotEvent::new(QUICKSAVE_PATH));
+    }
+}
+
+fn save_snapshots(
+    mut events: EventReader<SaveSnapshotEvent>,
+    clock: Res<SimulationClock>,
+    mouse_settings: Res<MouseSettings>,
+    bodies: Query<SavedBody>,
+    origin: Res<FloatingOrigin>,
+    coordinates: Query<&SimulationCoordinates>,
+) {
+    for event in events.iter() {
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            epoch: clock.epoch(),
+            elapsed: clock.elapsed,
+            steps: clock.steps,
+            bodies: bodies.iter().map(body_snapshot).collect(),
+            player: origin
+                .anchor
+                .and_then(|anchor| coordinates.get(anchor).ok())
+                .map(PlayerSnapshot::from),
+            yaw_pitch_roll: mouse_settings.yaw_pitch_roll,
+        };
+        match snapshot.save(&**event) {
+            Ok(()) => info!("Saved snapshot to {}", event.display()),
+            Err(err) => error!("Failed to save snapshot to {}: {}", event.display(), err),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn load_snapshots(
+    mut commands: Commands,
+    mut meshes: Option<ResMut<Assets<Mesh>>>,
+    mut materials: Option<ResMut<Assets<StandardMaterial>>>,
+    mut events: EventReader<LoadSnapshotEvent>,
+    mut clock: ResMut<SimulationClock>,
+    rebasing: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
+    mut origin: ResMut<FloatingOrigin>,
+    mut mouse_settings: ResMut<MouseSettings>,
+    mut yaw_events: EventWriter<YawEvent>,
+    mut pitch_events: EventWriter<PitchEvent>,
+    mut rebase_events: EventWriter<OrginRebasingEvent>,
+    mut bodies: Query<(
+        Entity,
+        &Name,
+        &mut Mass,
+        &mut Position,
+        &mut Velocity,
+        &mut Acceleration,
+    )>,
+    mut q: QuerySet<(
+        Query<(Entity, &mut Transform), With<Mass>>,
+        Query<(&mut Transform, &mut SimulationCoordinates)>,
+        Query<&mut Transform, Rebased>,
+    )>,
+) {
+    let snapshot = match events.iter().last() {
+        Some(event) => match Snapshot::load(&**event) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                error!("Failed to load snapshot from {}: {}", event.display(), err);
+                return;
+            }
+        },
+        None => return,
+    };
+
+    clock.start = snapshot.epoch - snapshot.elapsed;
+    clock.elapsed = snapshot.elapsed;
+    clock.steps = snapshot.steps;
+
+    let mut saved: HashMap<&str, &BodySnapshot> = snapshot
+        .bodies
+        .iter()
+        .map(|body| (body.definition.name.as_str(), body))
+        .collect();
+    let mut rotations = HashMap::new();
+
+    for (entity, name, mut mass, mut pos, mut vel, mut acc) in bodies.iter_mut() {
+        match saved.remove(name.as_str()) {
+            Some(body) => {
+                mass.0 = body.definition.mass;
+                *pos = body.definition.position;
+                *vel = body.definition.velocity;
+                *acc = Acceleration::ZERO;
+                rotations.insert(entity, body.rotation);
+                body.definition
+                    .insert_properties_into(&mut commands.entity(entity));
+            }
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+    for (entity, mut transform) in q.q0_mut().iter_mut() {
+        if let Some(rotation) = rotations.get(&entity) {
+            transform.rotation = *rotation;
+        }
+    }
+    // Respawned the way the scene spawned them, with meshes unless the app is headless
+    for body in saved.values() {
+        let entity = match (meshes.as_deref_mut(), materials.as_deref_mut()) {
+            (Some(meshes), Some(materials)) => {
+                spawn_body(&mut commands, meshes, materials, &scale, &body.definition)
+            }
+            _ => {
+                let mut entity = commands.spawn();
+                body.definition.insert_into(&mut entity);
+                entity.id()
+            }
+        };
+        commands.entity(entity).insert(Transform {
+            rotation: body.rotation,
+            ..Transform::from_translation(scale.position(body.definition.position.0))
+        });
+    }
+
+    // Rebase onto the player's saved cell like a teleport would, so everything else keeps its
+    // place relative to the player and registered `Rebaseable` state follows as well.
+    if let Some(player) = &snapshot.player {
+        let restored: SimulationCoordinates = player.into();
+        let has_player =
+            matches!(origin.anchor, Some(anchor) if q.q1_mut().get_mut(anchor).is_ok());
+        if has_player && restored.cell() != origin.cell {
+            let shift = SimulationCoordinates::new(origin.cell, Vec3::ZERO)
+                .cell_shift(&restored, rebasing.render_cell_size(&scale));
+            let old_cell = origin.cell;
+            origin.cell = restored.cell();
+            rebase_events.send(OrginRebasingEvent::new(old_cell, origin.cell, &shift));
+            for mut transform in q.q2_mut().iter_mut() {
+                transform.translation += shift;
+            }
+        }
+        if let Some((mut transform, mut coordinates)) = origin
+            .anchor
+            .and_then(|anchor| q.q1_mut().get_mut(anchor).ok())
+        {
+            transform.translation = restored.local_translation();
+            *coordinates = restored;
+        }
+    }
+
+    mouse_settings.yaw_pitch_roll = snapshot.yaw_pitch_roll;
+    yaw_events.send(YawEvent::new(snapshot.yaw_pitch_roll.x));
+    pitch_events.send(PitchEvent::new(snapshot.yaw_pitch_roll.y));
+}
@@ -0,0 +1,87 @@
+use crate::epoch::{Epoch, TimeScale};
+use bevy::math::DVec3;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrajectoryFormat {
+    Csv,
+    JsonLines,
+}
+
+impl FromStr for TrajectoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(TrajectoryFormat::Csv),
+            "jsonl" | "json-lines" | "ndjson" => Ok(TrajectoryFormat::JsonLines),
+            _ => Err(format!("Unknown trajectory format '{}'", s)),
+        }
+    }
+}
+
+/// State vector of one body at one point in simulated time, in SI units.
+#[derive(Debug, Serialize)]
+pub struct TrajectorySample<'a> {
+    pub time: f64,
+    pub epoch: Epoch,
+    pub body: &'a str,
+    pub position: DVec3,
+    pub velocity: DVec3,
+}
+
+pub struct TrajectoryWriter {
+    format: TrajectoryFormat,
+    out: Box<dyn Write + Send + Sync>,
+    header_written: bool,
+}
+
+impl TrajectoryWriter {
+    pub fn new(out: impl Write + Send + Sync + 'static, format: TrajectoryFormat) -> Self {
+        Self {
+            format,
+            out: Box::new(out),
+            header_written: false,
+        }
+    }
+
+    pub fn create(path: impl AsRef<Path>, format: TrajectoryFormat) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), format))
+    }
+
+    pub fn write(&mut self, sample: &TrajectorySample) -> io::Result<()> {
+        match self.format {
+            TrajectoryFormat::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "time,epoch,body,x,y,z,vx,vy,vz")?;
+                    self.header_written = true;
+                }
+                writeln!(
+                    self.out,
+                    "{},{},{},{},{},{},{},{},{}",
+                    sample.time,
+                    sample.epoch.to_iso8601(TimeScale::Tdb),
+                    sample.body,
+                    sample.position.x,
+                    sample.position.y,
+                    sample.position.z,
+                    sample.velocity.x,
+                    sample.velocity.y,
+                    sample.velocity.z
+                )
+            }
+            TrajectoryFormat::JsonLines => {
+                serde_json::to_writer(&mut self.out, sample)?;
+                writeln!(self.out)
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
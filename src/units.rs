@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+pub use bevy_world_scale::{METERS_PER_ASTRONOMICAL_UNIT, METERS_PER_KILOMETER};
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+// Quantities of the same unit add and subtract, scale by plain numbers, and divide into a ratio.
+// Mixing units has to go through an explicit conversion.
+macro_rules! quantity {
+    ($name:ident, $symbol:expr) => {
+        #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub f64);
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+
+            fn mul(self, rhs: $name) -> $name {
+                $name(self * rhs.0)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $symbol)
+            }
+        }
+    };
+}
+
+// Both directions of a conversion between two units of the same dimension.
+macro_rules! conversion {
+    ($from:ident, $to:ident, $factor:expr) => {
+        impl From<$from> for $to {
+            fn from(value: $from) -> Self {
+                Self(value.0 * $factor)
+            }
+        }
+
+        impl From<$to> for $from {
+            fn from(value: $to) -> Self {
+                Self(value.0 / $factor)
+            }
+        }
+    };
+}
+
+quantity!(Meters, "m");
+quantity!(Kilometers, "km");
+quantity!(AstronomicalUnits, "au");
+quantity!(Kilograms, "kg");
+quantity!(Seconds, "s");
+quantity!(MetersPerSecond, "m/s");
+quantity!(MetersPerSecondSquared, "m/s^2");
+
+conversion!(Kilometers, Meters, METERS_PER_KILOMETER);
+conversion!(AstronomicalUnits, Meters, METERS_PER_ASTRONOMICAL_UNIT);
+conversion!(
+    AstronomicalUnits,
+    Kilometers,
+    METERS_PER_ASTRONOMICAL_UNIT / METERS_PER_KILOMETER
+);
+
+impl Seconds {
+    pub fn from_days(days: f64) -> Self {
+        Self(days * SECONDS_PER_DAY)
+    }
+
+    pub fn days(self) -> f64 {
+        self.0 / SECONDS_PER_DAY
+    }
+}
+
+impl Div<Seconds> for Meters {
+    type Output = MetersPerSecond;
+
+    fn div(self, rhs: Seconds) -> MetersPerSecond {
+        MetersPerSecond(self.0 / rhs.0)
+    }
+}
+
+impl Mul<Seconds> for MetersPerSecond {
+    type Output = Meters;
+
+    fn mul(self, rhs: Seconds) -> Meters {
+        Meters(self.0 * rhs.0)
+    }
+}
+
+impl Div<MetersPerSecond> for Meters {
+    type Output = Seconds;
+
+    fn div(self, rhs: MetersPerSecond) -> Seconds {
+        Seconds(self.0 / rhs.0)
+    }
+}
@@ -1,21 +1,28 @@
 use crate::cameras::tag::*;
 use crate::controllers::tag::*;
+use crate::gravity::resources::SimulationClock;
 use crate::look::*;
-use crate::scale::{convert_metres_to_units, KM_TO_UNIT_SCALE, M_TO_UNIT_SCALE};
+use crate::scale::*;
+use crate::units::*;
 use bevy::prelude::*;
 use bevy::render::camera::{ActiveCameras, Camera, CameraProjection, PerspectiveProjection};
 use bevy::render::pipeline::{PipelineDescriptor, RenderPipeline};
 use bevy::render::render_graph::base::MainPass;
 use bevy::render::shader::ShaderStages;
 use bevy::render::wireframe::Wireframe;
-use bevy::window::WindowId;
+use bevy::window::{WindowId, Windows};
 use bevy_dynamic_billboarding::tags::{BillboardTag, FirstPass};
-use bevy_dynamic_billboarding::{FIRST_PASS_CAMERA, RENDER_TEXTURE_HANDLE};
-use bevy_origin_rebasing::{NonPlayerTag, PlayerTag, SimulationBundle, SimulationCoordinates};
+use bevy_dynamic_billboarding::{DepthSettings, FIRST_PASS_CAMERA, RENDER_TEXTURE_HANDLE};
+use bevy_origin_rebasing::{
+    CompressedSimulationBundle, FloatingOriginAnchor, NonPlayerTag, OriginRebasingSettings,
+    SimulationBundle, SimulationCoordinates, ViewInstance, ViewOrigin,
+};
 use rand::Rng;
 
 pub struct CharacterSettings {
+    /// Body size in metres.
     pub scale: Vec3,
+    /// Head size in metres.
     pub head_scale: f32,
     pub head_yaw: f32,
     pub follow_offset: Vec3,
@@ -25,8 +32,8 @@ pub struct CharacterSettings {
 impl Default for CharacterSettings {
     fn default() -> Self {
         Self {
-            scale: convert_metres_to_units(Vec3::new(0.3, 0.5, 1.9)),
-            head_scale: 0.3 * M_TO_UNIT_SCALE,
+            scale: Vec3::new(0.3, 0.5, 1.9),
+            head_scale: 0.3,
             head_yaw: 0.0,
             follow_offset: Vec3::new(0.0, 0.0, 0.0), // Relative to head
             focal_point: Vec3::ZERO,                 // Relative to head
@@ -37,10 +44,16 @@ impl Default for CharacterSettings {
 pub fn spawn_character(
     mut commands: Commands,
     character_settings: Res<CharacterSettings>,
+    world_scale: Res<WorldScale>,
+    depth: Res<DepthSettings>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    let box_y = 1.0;
+    let metre = world_scale.to_render_units(Meters(1.0));
+    let scale = character_settings.scale * metre;
+    let head_scale = character_settings.head_scale * metre;
+    let box_y = metre;
+    let eye_level = 1.695 * metre;
     let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
     let red = materials.add(Color::hex("800000").unwrap().into());
 
@@ -49,14 +62,9 @@ pub fn spawn_character(
             material: red.clone(),
             mesh: cube.clone(),
             transform: Transform::from_matrix(Mat4::from_scale_rotation_translation(
-                character_settings.scale - character_settings.head_scale * Vec3::Y,
+                scale - head_scale * Vec3::Y,
                 Quat::IDENTITY,
-                Vec3::new(
-                    0.0,
-                    0.5 * (box_y + character_settings.scale.y - character_settings.head_scale)
-                        - 1.695,
-                    0.0,
-                ),
+                Vec3::new(0.0, 0.5 * (box_y + scale.y - head_scale) - eye_level, 0.0),
             )),
             ..Default::default()
         })
@@ -70,7 +78,7 @@ pub fn spawn_character(
         .spawn_bundle((GlobalTransform::identity(), r, BodyTag))
         .insert(Name::new("player"))
         .insert(SimulationCoordinates::default())
-        .insert(PlayerTag)
+        .insert(FloatingOriginAnchor)
         .id();
 
     let yaw = commands
@@ -83,12 +91,7 @@ pub fn spawn_character(
             Transform::from_matrix(Mat4::from_scale_rotation_translation(
                 Vec3::ONE,
                 Quat::from_rotation_y(character_settings.head_yaw),
-                Vec3::new(
-                    0.0,
-                    0.5 * (box_y - character_settings.head_scale) + character_settings.scale.y
-                        - 1.695,
-                    0.0,
-                ),
+                Vec3::new(0.0, 0.5 * (box_y - head_scale) + scale.y - eye_level, 0.0),
             )),
             HeadTag,
         ))
@@ -98,7 +101,7 @@ pub fn spawn_character(
         .spawn_bundle(PbrBundle {
             material: red,
             mesh: cube,
-            transform: Transform::from_scale(Vec3::splat(character_settings.head_scale)),
+            transform: Transform::from_scale(Vec3::splat(head_scale)),
             ..Default::default()
         })
         .insert(Wireframe)
@@ -117,7 +120,7 @@ pub fn spawn_character(
                 Vec3::Y,
             )),
             perspective_projection: PerspectiveProjection {
-                far: 1_000_000_000.0,
+                far: depth.far,
                 ..Default::default()
             },
             ..Default::default()
@@ -134,6 +137,23 @@ pub fn spawn_character(
     commands.entity(head).push_children(&[head_model, camera]);
 }
 
+/// `SPACE_WORLD_SCALE` overrides the render units per metre, e.g. `SPACE_WORLD_SCALE=1e-6`.
+pub fn world_scale_from_env() -> WorldScale {
+    std::env::var("SPACE_WORLD_SCALE")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .map(WorldScale)
+        .unwrap_or_default()
+}
+
+pub fn show_epoch_in_title(clock: Res<SimulationClock>, mut windows: ResMut<Windows>) {
+    if clock.is_changed() {
+        if let Some(window) = windows.get_primary_mut() {
+            window.set_title(format!("space - {}", clock.epoch()));
+        }
+    }
+}
+
 pub fn spawn_lights(mut commands: Commands) {
     commands.spawn_bundle(LightBundle {
         transform: Transform::from_translation(Vec3::new(-10.0, 0.0, -10.0)),
@@ -209,10 +229,12 @@ pub fn spawn_world(
 
 pub fn spawn_earth(
     mut commands: Commands,
+    rebasing: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    let radius = 6051.84 * KM_TO_UNIT_SCALE;
+    let radius = scale.to_render_units(Kilometers(6051.84));
     commands
         .spawn_bundle(PbrBundle {
             material: materials.add(StandardMaterial {
@@ -226,7 +248,11 @@ pub fn spawn_earth(
             })),
             ..Default::default()
         })
-        .insert_bundle(SimulationBundle::new_scaled(-Vec3::Z * radius * 4.0))
+        .insert_bundle(CompressedSimulationBundle::new(
+            -Vec3::Z * radius * 4.0,
+            &rebasing,
+            &scale,
+        ))
         .insert(Wireframe)
         .insert(NonPlayerTag)
         .insert(Name::new("Marker"));
@@ -234,10 +260,12 @@ pub fn spawn_earth(
 
 pub fn spawn_marker_billboard(
     mut commands: Commands,
+    rebasing: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    let radius = 6051.84 * KM_TO_UNIT_SCALE;
+    let radius = scale.to_render_units(Kilometers(6051.84));
     commands
         .spawn_bundle(PbrBundle {
             material: materials.add(StandardMaterial {
@@ -251,7 +279,11 @@ pub fn spawn_marker_billboard(
             })),
             ..Default::default()
         })
-        .insert_bundle(SimulationBundle::new(-Vec3::Z * radius * 4.0))
+        .insert_bundle(SimulationBundle::new(
+            -Vec3::Z * radius * 4.0,
+            &rebasing,
+            &scale,
+        ))
         .insert(Wireframe)
         .insert(NonPlayerTag)
         .insert(Name::new("MarkerBillboard"));
@@ -259,33 +291,17 @@ pub fn spawn_marker_billboard(
 
 pub fn spawn_earth_billboard(
     mut commands: Commands,
+    rebasing: Res<OriginRebasingSettings>,
+    scale: Res<WorldScale>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut active_cameras: ResMut<ActiveCameras>,
     asset_server: ResMut<AssetServer>,
     mut pipelines: ResMut<Assets<PipelineDescriptor>>,
+    mut shaders: ResMut<Assets<Shader>>,
+    depth: Res<DepthSettings>,
 ) {
     let radius = 2.0;
-    commands
-        .spawn_bundle(PbrBundle {
-            material: materials.add(StandardMaterial {
-                base_color: Color::NAVY.into(),
-                roughness: 0.6,
-                ..Default::default()
-            }),
-            mesh: meshes.add(Mesh::from(shape::Icosphere {
-                radius: radius,
-                subdivisions: 20,
-            })),
-            ..Default::default()
-        })
-        .insert(Wireframe)
-        .insert(NonPlayerTag)
-        .insert(BillboardTag)
-        .insert(Name::new("Earth"))
-        .insert(FirstPass)
-        .remove::<MainPass>();
-
     let mut first_pass_camera = PerspectiveCameraBundle {
         camera: Camera {
             name: Some(FIRST_PASS_CAMERA.to_string()),
@@ -304,7 +320,39 @@ pub fn spawn_earth_billboard(
     first_pass_camera.camera.projection_matrix = camera_projection.get_projection_matrix();
     first_pass_camera.camera.depth_calculation = camera_projection.depth_calculation();
 
-    commands.spawn_bundle(first_pass_camera);
+    // The first pass camera has its own origin, the shared one follows the player
+    let first_pass_camera = commands
+        .spawn_bundle(first_pass_camera)
+        .insert(ViewOrigin::default())
+        .id();
+    let earth = commands
+        .spawn_bundle((
+            Transform::identity(),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+        ))
+        .insert(NonPlayerTag)
+        .insert(Name::new("Earth"))
+        .id();
+
+    commands
+        .spawn_bundle(PbrBundle {
+            material: materials.add(StandardMaterial {
+                base_color: Color::NAVY.into(),
+                roughness: 0.6,
+                ..Default::default()
+            }),
+            mesh: meshes.add(Mesh::from(shape::Icosphere {
+                radius: radius,
+                subdivisions: 20,
+            })),
+            ..Default::default()
+        })
+        .insert(Wireframe)
+        .insert(BillboardTag)
+        .insert(FirstPass)
+        .insert(ViewInstance::new(first_pass_camera, earth))
+        .remove::<MainPass>();
 
     let texture_handle: Handle<Texture> = RENDER_TEXTURE_HANDLE.typed();
 
@@ -318,14 +366,25 @@ pub fn spawn_earth_billboard(
 
     asset_server.watch_for_changes().unwrap();
 
-    let pipeline = PipelineDescriptor::default_config(ShaderStages {
-        vertex: asset_server.load::<Shader, _>("billboard.vert"),
-        fragment: Some(asset_server.load::<Shader, _>("billboard.frag")),
-    });
+    // Hot reloading only works with the assets, logarithmic depth needs its code baked in
+    let stages = if depth.logarithmic {
+        ShaderStages {
+            vertex: shaders.add(depth.vertex_shader(include_str!("../assets/billboard.vert"))),
+            fragment: Some(
+                shaders.add(depth.fragment_shader(include_str!("../assets/billboard.frag"))),
+            ),
+        }
+    } else {
+        ShaderStages {
+            vertex: asset_server.load::<Shader, _>("billboard.vert"),
+            fragment: Some(asset_server.load::<Shader, _>("billboard.frag")),
+        }
+    };
+    let pipeline = PipelineDescriptor::default_config(stages);
 
     let pipeline_handle = pipelines.add(pipeline);
 
-    let radius = 6051.84 * KM_TO_UNIT_SCALE;
+    let radius = scale.to_render_units(Kilometers(6051.84));
     commands
         .spawn_bundle(PbrBundle {
             mesh: meshes.add(Mesh::from(shape::Quad {
@@ -343,6 +402,10 @@ pub fn spawn_earth_billboard(
             ..Default::default()
         })
         .insert(Wireframe)
-        .insert_bundle(SimulationBundle::new_scaled(-Vec3::Z * radius * 4.0))
+        .insert_bundle(CompressedSimulationBundle::new(
+            -Vec3::Z * radius * 4.0,
+            &rebasing,
+            &scale,
+        ))
         .insert(Name::new("RealBillboard"));
 }
@@ -0,0 +1,136 @@
+use space::epoch::*;
+
+const MILLISECOND: f64 = 1e-3;
+
+fn epoch(s: &str) -> Epoch {
+    s.parse().unwrap()
+}
+
+#[test]
+fn j2000_in_every_time_scale() {
+    let j2000 = epoch("2000-01-01T12:00:00 TT");
+    assert_eq!(j2000.to_iso8601(TimeScale::Utc), "2000-01-01T11:58:55.816Z");
+    assert_eq!(
+        j2000.to_iso8601(TimeScale::Tai),
+        "2000-01-01T11:59:27.816 TAI"
+    );
+    assert_eq!(
+        j2000.to_iso8601(TimeScale::Tt),
+        "2000-01-01T12:00:00.000 TT"
+    );
+    // TDB and TT differ by less than 2 ms
+    assert!((j2000 - Epoch::J2000).abs() < 2.0 * MILLISECOND);
+    assert_eq!(Epoch::J2000.julian_date(TimeScale::Tdb), J2000_JULIAN_DATE);
+}
+
+#[test]
+fn julian_dates_round_trip_in_every_time_scale() {
+    let scales = [
+        TimeScale::Utc,
+        TimeScale::Tai,
+        TimeScale::Tt,
+        TimeScale::Tdb,
+    ];
+    for jd in [2_415_020.5, J2000_JULIAN_DATE, 2_459_322.5, 2_469_807.25] {
+        for scale in scales {
+            let round_trip = Epoch::from_julian_date(jd, scale).julian_date(scale);
+            // Half a millisecond, about the precision of an f64 Julian date
+            assert!(
+                (round_trip - jd).abs() < 1e-8,
+                "JD {} {:?} came back as {}",
+                jd,
+                scale,
+                round_trip
+            );
+        }
+    }
+}
+
+#[test]
+fn utc_tai_tt_tdb_chain() {
+    let utc = epoch("2021-04-18T00:00:00Z");
+    let tai = epoch("2021-04-18T00:00:37 TAI");
+    let tt = epoch("2021-04-18T00:01:09.184 TT");
+    assert!((utc - tai).abs() < MILLISECOND);
+    assert!((tai - tt).abs() < MILLISECOND);
+
+    let tdb = utc.to_iso8601(TimeScale::Tdb);
+    assert!(tdb.starts_with("2021-04-18T00:01:09.18"), "{}", tdb);
+    assert!((epoch(&tdb) - utc).abs() < MILLISECOND);
+    assert_eq!(
+        epoch(&tdb).to_iso8601(TimeScale::Utc),
+        "2021-04-18T00:00:00.000Z"
+    );
+}
+
+#[test]
+fn leap_second_round_trips() {
+    let before = epoch("2016-12-31T23:59:59Z");
+    let leap = epoch("2016-12-31T23:59:60Z");
+    let after = epoch("2017-01-01T00:00:00Z");
+
+    assert!((leap - before - 1.0).abs() < MILLISECOND);
+    assert!((after - leap - 1.0).abs() < MILLISECOND);
+    assert_eq!(leap.to_iso8601(TimeScale::Utc), "2016-12-31T23:59:60.000Z");
+    assert_eq!(
+        leap.to_iso8601(TimeScale::Tai),
+        "2017-01-01T00:00:36.000 TAI"
+    );
+    assert_eq!(
+        after.to_iso8601(TimeScale::Tai),
+        "2017-01-01T00:00:37.000 TAI"
+    );
+    let date_time = (leap + 0.5).date_time(TimeScale::Utc);
+    assert_eq!((date_time.hour, date_time.minute), (23, 59));
+    assert!((date_time.second - 60.5).abs() < MILLISECOND);
+}
+
+#[test]
+fn offsets_and_serde() {
+    assert!(
+        (epoch("2021-04-18T02:30:00+02:30") - epoch("2021-04-18T00:00:00Z")).abs() < MILLISECOND
+    );
+
+    let epoch = epoch("2021-04-18T00:00:00 TDB");
+    let json = serde_json::to_string(&epoch).unwrap();
+    assert_eq!(json, "\"2021-04-18T00:00:00.000 TDB\"");
+    assert_eq!(serde_json::from_str::<Epoch>(&json).unwrap(), epoch);
+}
+
+#[test]
+fn malformed_dates_are_rejected() {
+    for s in [
+        "",
+        "not a date",
+        "2021-13-01T00:00:00Z",
+        "2021-02-29T00:00:00Z",
+        "2021-04-18T24:00:00Z",
+        "2021-04-18T00:60:00Z",
+        "2021-04-18T00:00:61Z",
+        "2021-04-18T00:00:00 XYZ",
+        "2021-04-18T00:00:00+01:00 TDB",
+        "-0001-01-01T00:00:00Z",
+        "+10000-01-01T00:00:00Z",
+        "21-04-18T00:00:00Z",
+    ] {
+        assert!(s.parse::<Epoch>().is_err(), "'{}' parsed", s);
+    }
+}
+
+#[test]
+fn leap_seconds_are_only_accepted_where_the_table_has_them() {
+    for s in [
+        "2022-04-18T12:00:60Z",
+        "2022-04-18T23:59:60Z",
+        "2016-12-30T23:59:60Z",
+        "2016-12-31T23:58:60Z",
+        "2016-12-31T23:59:60 TAI",
+        "2017-01-01T00:59:60+01:00",
+        "1971-12-31T23:59:60Z",
+    ] {
+        assert!(s.parse::<Epoch>().is_err(), "'{}' parsed", s);
+    }
+    for s in ["1972-06-30T23:59:60Z", "2016-12-31T23:59:60.5Z"] {
+        assert!(s.parse::<Epoch>().is_ok(), "'{}' was rejected", s);
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_origin_rebasing::*;
+use space::gravity::{body::*, lagrange::*, nbody::NBodyPlugin};
+use space::units::*;
+
+const CELL_SIZE: f64 = 2_000.0;
+const AU: f64 = METERS_PER_ASTRONOMICAL_UNIT;
+
+fn spawn_pair(mut commands: Commands) {
+    let sun = commands
+        .spawn_bundle(BodyBundle::new(
+            Kilograms(1.9885e30),
+            Position::ZERO,
+            Velocity::ZERO,
+        ))
+        .id();
+    let earth = commands
+        .spawn_bundle(BodyBundle::new(
+            Kilograms(5.972e24),
+            Position::new(AU, 0.0, 0.0),
+            Velocity::new(0.0, 29_780.0, 0.0),
+        ))
+        .id();
+    spawn_lagrange_points(&mut commands, "sun-earth", LagrangePair::new(sun, earth));
+}
+
+fn app() -> AppBuilder {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(OriginRebasingPlugin::new(OriginRebasingSettings {
+            cell_size: CELL_SIZE,
+            draw_debug_cell: false,
+            ..Default::default()
+        }))
+        .add_plugin(NBodyPlugin)
+        .add_plugin(LagrangePlugin)
+        .add_startup_system(spawn_pair.system());
+    app
+}
+
+#[test]
+fn lagrange_points_are_placed_relative_to_the_origin_cell() {
+    let mut app = app();
+    // Centre the render origin on the earth, as if the player were there
+    let earth_cell = GridCell::new((AU / CELL_SIZE).round() as i64, 0, 0);
+    app.world_mut()
+        .get_resource_mut::<FloatingOrigin>()
+        .unwrap()
+        .cell = earth_cell;
+    app.app.update();
+    app.app.update();
+
+    let origin = SimulationCoordinates::new(earth_cell, Vec3::ZERO);
+    let world = app.world_mut();
+    let mut points = world.query::<(
+        &LagrangePoint,
+        &Position,
+        &SimulationCoordinates,
+        &Transform,
+    )>();
+    let mut count = 0;
+    for (point, pos, coordinates, transform) in points.iter(world) {
+        let expected = SimulationCoordinates::from_translation(pos.0, CELL_SIZE);
+        assert_eq!(*coordinates, expected, "{:?}", point);
+        assert_eq!(
+            transform.translation,
+            expected.relative_translation(&origin, CELL_SIZE)
+        );
+        count += 1;
+    }
+    assert_eq!(count, 5);
+
+    // L1 and L2 are about 1.5 million km from the earth, not an AU from the render origin
+    let (_, _, _, l1) = points
+        .iter(world)
+        .find(|(point, ..)| **point == LagrangePoint::L1)
+        .unwrap();
+    assert!((l1.translation.length() - 1.5e9).abs() < 0.1e9);
+}
+
+#[test]
+fn lagrange_points_stay_between_their_bodies_after_a_rebase() {
+    let mut app = app();
+    let player = app
+        .world_mut()
+        .spawn()
+        .insert_bundle((
+            Transform::identity(),
+            GlobalTransform::identity(),
+            SimulationCoordinates::default(),
+            FloatingOriginAnchor,
+        ))
+        .id();
+    app.app.update();
+    app.world_mut()
+        .get_mut::<SimulationCoordinates>(player)
+        .unwrap()
+        .teleport(DVec3::new(AU, 0.0, 0.0));
+    app.app.update();
+    app.app.update();
+    assert_ne!(
+        app.world_mut()
+            .get_resource::<FloatingOrigin>()
+            .unwrap()
+            .cell,
+        GridCell::ZERO
+    );
+
+    let world = app.world_mut();
+    let mut points = world.query::<(&LagrangePoint, &LagrangePair, &Transform)>();
+    let (_, pair, l1) = points
+        .iter(world)
+        .find(|(point, ..)| **point == LagrangePoint::L1)
+        .unwrap();
+    let (pair, l1) = (*pair, l1.translation);
+    let sun = world.get::<Transform>(pair.primary).unwrap().translation;
+    let earth = world.get::<Transform>(pair.secondary).unwrap().translation;
+
+    let along = (l1 - sun).dot(earth - sun) / (earth - sun).length_squared();
+    assert!(along > 0.98 && along < 1.0, "{}", along);
+    assert!(((l1 - earth).length() - 1.5e9).abs() < 0.1e9);
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use space::gravity::{body::*, nbody::NBodyPlugin, resources::*};
+use space::units::*;
+use std::f64::consts::TAU;
+
+const EARTH_MASS: f64 = 5.972e24;
+const EARTH_RADIUS: f64 = 6.371e6;
+
+fn app_with_earth(timestep: f64) -> (AppBuilder, ParentBody) {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(NBodyPlugin)
+        .insert_resource(SimulationSettings {
+            timestep: Seconds(timestep),
+            integrator: Integrator::Leapfrog,
+        });
+    let earth = app
+        .world_mut()
+        .spawn()
+        .insert_bundle(BodyBundle::new(
+            Kilograms(EARTH_MASS),
+            Position::ZERO,
+            Velocity::ZERO,
+        ))
+        .insert(Radius(Meters(EARTH_RADIUS)))
+        .id();
+    let parent = ParentBody::from_world(app.world_mut(), earth).unwrap();
+    (app, parent)
+}
+
+fn spawn(app: &mut AppBuilder, bundle: BodyBundle) -> Entity {
+    app.world_mut().spawn().insert_bundle(bundle).id()
+}
+
+/// Specific orbital energy `v^2 / 2 - mu / r` relative to the parent.
+fn specific_energy(app: &mut AppBuilder, parent: &ParentBody, body: Entity) -> f64 {
+    let world = app.world_mut();
+    let r = *world.get::<Position>(body).unwrap() - parent.position;
+    let v = *world.get::<Velocity>(body).unwrap() - parent.velocity;
+    v.0.length_squared() / 2.0 - parent.gravitational_parameter() / r.length().0
+}
+
+#[test]
+fn circular_orbit_returns_after_one_period() {
+    let (mut app, parent) = app_with_earth(1.0);
+    let radius = 7.0e6;
+    let body = spawn(
+        &mut app,
+        BodyBundle::circular_orbit_around(&parent, Meters(radius), 0.5),
+    );
+    let start = app.world_mut().get::<Position>(body).unwrap().0;
+
+    let period = TAU * (radius.powi(3) / parent.gravitational_parameter()).sqrt();
+    for _ in 0..period.round() as u64 {
+        app.app.update();
+    }
+
+    let end = app.world_mut().get::<Position>(body).unwrap().0;
+    assert!((end.length() - radius).abs() < 1.0, "{}", end.length());
+    assert!(
+        start.distance(end) < 1e-3 * radius,
+        "{} m away from the start after one period",
+        start.distance(end)
+    );
+}
+
+#[test]
+fn elliptic_orbit_has_the_energy_of_its_semi_major_axis() {
+    let (mut app, parent) = app_with_earth(1.0);
+    let (periapsis, apoapsis) = (7.0e6, 4.2e7);
+    let body = spawn(
+        &mut app,
+        BodyBundle::elliptic(&parent, Meters(periapsis), Meters(apoapsis)),
+    );
+
+    let semi_major_axis = (periapsis + apoapsis) / 2.0;
+    let expected = -parent.gravitational_parameter() / (2.0 * semi_major_axis);
+    let energy = specific_energy(&mut app, &parent, body);
+    assert!(
+        ((energy - expected) / expected).abs() < 1e-12,
+        "{} J/kg, expected {}",
+        energy,
+        expected
+    );
+}
+
+#[test]
+fn escape_trajectory_leaves_with_v_inf() {
+    let (mut app, parent) = app_with_earth(1.0);
+    let surface_energy = parent.gravitational_parameter() / EARTH_RADIUS;
+
+    let parabolic = BodyBundle::escape_trajectory(&parent, MetersPerSecond(0.0)).unwrap();
+    let parabolic = spawn(&mut app, parabolic);
+    let energy = specific_energy(&mut app, &parent, parabolic);
+    assert!(energy.abs() < 1e-12 * surface_energy, "{} J/kg", energy);
+
+    let v_inf = 3_000.0;
+    let hyperbolic = BodyBundle::escape_trajectory(&parent, MetersPerSecond(v_inf)).unwrap();
+    let hyperbolic = spawn(&mut app, hyperbolic);
+    let energy = specific_energy(&mut app, &parent, hyperbolic);
+    assert!(
+        (energy - v_inf * v_inf / 2.0).abs() < 1e-12 * surface_energy,
+        "{} J/kg",
+        energy
+    );
+}
+
+#[test]
+fn escape_trajectory_needs_a_radius() {
+    let parent = ParentBody {
+        mass: Kilograms(EARTH_MASS),
+        position: Position::ZERO,
+        velocity: Velocity::ZERO,
+        radius: Radius(Meters(0.0)).surface(),
+    };
+    assert!(BodyBundle::escape_trajectory(&parent, MetersPerSecond(0.0)).is_none());
+}
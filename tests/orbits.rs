@@ -0,0 +1,156 @@
+use bevy::{math::DVec3, prelude::*};
+use space::gravity::{body::*, nbody::NBodyPlugin, resources::*, system::SystemDefinition};
+use space::units::*;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+const DAY: f64 = SECONDS_PER_DAY;
+const AU: f64 = METERS_PER_ASTRONOMICAL_UNIT;
+const SIDEREAL_YEAR: f64 = 365.256_363 * DAY;
+
+// Sidereal periods in days from the NASA planetary fact sheets
+const SIDEREAL_PERIODS: [(&str, f64); 9] = [
+    ("mercury", 87.969),
+    ("venus", 224.701),
+    ("earth", 365.256),
+    ("mars", 686.980),
+    ("jupiter", 4_332.589),
+    ("saturn", 10_759.22),
+    ("uranus", 30_685.4),
+    ("neptune", 60_189.0),
+    ("pluto", 90_560.0),
+];
+
+fn solar_system_app(timestep: f64, system: SystemDefinition) -> AppBuilder {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(NBodyPlugin)
+        .insert_resource(SimulationSettings {
+            timestep: Seconds(timestep),
+            integrator: Integrator::Leapfrog,
+        })
+        .insert_resource(system)
+        .add_startup_system(spawn_system.system());
+    // Spawns the bodies and takes the first step
+    app.app.update();
+    app
+}
+
+fn spawn_system(mut commands: Commands, system: Res<SystemDefinition>) {
+    system.spawn(&mut commands);
+}
+
+fn heliocentric_positions(app: &mut AppBuilder) -> HashMap<String, DVec3> {
+    let world = app.world_mut();
+    let mut query = world.query::<(&Name, &Position)>();
+    let positions: HashMap<_, _> = query
+        .iter(world)
+        .map(|(name, pos)| (name.as_str().to_string(), pos.0))
+        .collect();
+    let sun = positions["sun"];
+    positions
+        .into_iter()
+        .map(|(name, pos)| (name, pos - sun))
+        .collect()
+}
+
+/// The solar system with only the sun and `planets`, the rest weigh too little to matter for
+/// their periods but would need a much finer step.
+fn sun_and(planets: &[(&str, f64)]) -> SystemDefinition {
+    let mut system = SystemDefinition::solar_system();
+    system
+        .bodies
+        .retain(|body| body.name == "sun" || planets.iter().any(|(name, _)| *name == body.name));
+    system
+}
+
+/// Steps the sun and `planets` until every planet has swept a full turn of ecliptic longitude,
+/// or a little past the longest expected period, and checks the periods.
+fn check_sidereal_periods(planets: &[(&str, f64)], timestep: f64) {
+    let mut app = solar_system_app(timestep, sun_and(planets));
+    let longest = planets
+        .iter()
+        .map(|(_, period)| *period)
+        .fold(0.0, f64::max);
+    let max_steps = (longest * 1.05 * DAY / timestep).ceil() as u64;
+
+    // Unwrapped ecliptic longitude swept by each planet, the period is when it reaches 2 pi
+    let mut previous = heliocentric_positions(&mut app);
+    let mut swept: HashMap<String, f64> = HashMap::new();
+    let mut periods: HashMap<String, f64> = HashMap::new();
+    let mut elapsed = 0.0;
+    for _ in 0..max_steps {
+        if periods.len() == planets.len() {
+            break;
+        }
+        app.app.update();
+        elapsed += timestep;
+        let current = heliocentric_positions(&mut app);
+        for (name, _) in planets.iter() {
+            if periods.contains_key(*name) {
+                continue;
+            }
+            let (from, to) = (previous[*name], current[*name]);
+            let step = from.x * to.y - from.y * to.x;
+            let angle = step.atan2(from.x * to.x + from.y * to.y);
+            let before = swept.get(*name).copied().unwrap_or(0.0);
+            let after = before + angle;
+            if after >= TAU {
+                let fraction = (TAU - before) / angle;
+                periods.insert(name.to_string(), elapsed - timestep * (1.0 - fraction));
+            }
+            swept.insert(name.to_string(), after);
+        }
+        previous = current;
+    }
+
+    for (name, expected) in planets.iter() {
+        let period = periods
+            .get(*name)
+            .unwrap_or_else(|| panic!("{} did not complete an orbit", name))
+            / DAY;
+        assert!(
+            ((period - expected) / expected).abs() < 0.01,
+            "{} took {} days to orbit, expected {}",
+            name,
+            period,
+            expected
+        );
+    }
+}
+
+#[test]
+fn inner_sidereal_periods() {
+    check_sidereal_periods(&SIDEREAL_PERIODS[..4], DAY);
+}
+
+// Jupiter still gets over 400 steps an orbit at this step, and Pluto takes under 10k of them
+#[test]
+fn outer_sidereal_periods() {
+    check_sidereal_periods(&SIDEREAL_PERIODS[4..], 10.0 * DAY);
+}
+
+#[test]
+fn earth_returns_after_one_sidereal_year() {
+    let steps = 8_766;
+    let mut app = solar_system_app(
+        SIDEREAL_YEAR / steps as f64,
+        SystemDefinition::solar_system(),
+    );
+
+    let start = heliocentric_positions(&mut app)["earth"];
+    for _ in 0..steps {
+        app.app.update();
+    }
+    let end = heliocentric_positions(&mut app)["earth"];
+
+    // The system has no Moon, so Earth's state still carries the monthly wobble around the
+    // Earth-Moon barycentre, which shifts its period by a few hours. A 1% error in G or the
+    // sun's mass shifts it by almost two days, which is about 0.03 AU.
+    let error = start.distance(end) / AU;
+    assert!(
+        error < 5e-3,
+        "Earth was {} AU away from its starting position after a sidereal year",
+        error
+    );
+}
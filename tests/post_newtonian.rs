@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use space::gravity::{body::*, nbody::NBodyPlugin, resources::*};
+use space::units::*;
+
+const SUN_MASS: f64 = 1.9885e30;
+const MERCURY_MASS: f64 = 3.302e23;
+const MERCURY_SEMI_MAJOR_AXIS: f64 = 5.7909e10;
+const MERCURY_ECCENTRICITY: f64 = 0.205_63;
+const JULIAN_YEAR: f64 = 365.25 * 86_400.0;
+const ARCSEC_PER_RADIAN: f64 = 180.0 * 3_600.0 / std::f64::consts::PI;
+
+/// Returns how far Mercury's perihelion moved in arcsec over `duration` simulated seconds.
+fn perihelion_shift(post_newtonian: bool, duration: f64) -> f64 {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(NBodyPlugin)
+        .insert_resource(PostNewtonian(post_newtonian))
+        .insert_resource(SimulationSettings {
+            timestep: Seconds(3_600.0),
+            integrator: Integrator::Leapfrog,
+        });
+
+    let mu = G * (SUN_MASS + MERCURY_MASS);
+    let perihelion = MERCURY_SEMI_MAJOR_AXIS * (1.0 - MERCURY_ECCENTRICITY);
+    let speed = (mu * (1.0 + MERCURY_ECCENTRICITY) / perihelion).sqrt();
+    app.world_mut().spawn().insert_bundle(BodyBundle::new(
+        Kilograms(SUN_MASS),
+        Position::ZERO,
+        Velocity::ZERO,
+    ));
+    app.world_mut().spawn().insert_bundle(BodyBundle::new(
+        Kilograms(MERCURY_MASS),
+        Position::new(perihelion, 0.0, 0.0),
+        Velocity::new(0.0, speed, 0.0),
+    ));
+
+    let start = perihelion_longitude(&mut app, mu);
+    let steps = (duration / 3_600.0).round() as u64;
+    for _ in 0..steps {
+        app.app.update();
+    }
+    let end = perihelion_longitude(&mut app, mu);
+
+    (end - start) * ARCSEC_PER_RADIAN
+}
+
+fn perihelion_longitude(app: &mut AppBuilder, mu: f64) -> f64 {
+    let world = app.world_mut();
+    let mut query = world.query::<(&Mass, &Position, &Velocity)>();
+    let mut sun = None;
+    let mut mercury = None;
+    for (mass, pos, vel) in query.iter(world) {
+        if mass.0 == Kilograms(SUN_MASS) {
+            sun = Some((pos.0, vel.0));
+        } else {
+            mercury = Some((pos.0, vel.0));
+        }
+    }
+    let (sun, mercury) = (sun.unwrap(), mercury.unwrap());
+    let r = mercury.0 - sun.0;
+    let v = mercury.1 - sun.1;
+    // Laplace-Runge-Lenz vector, which points at the perihelion
+    let eccentricity = (r * (v.length_squared() - mu / r.length()) - v * r.dot(v)) / mu;
+    eccentricity.y.atan2(eccentricity.x)
+}
+
+#[test]
+fn mercury_perihelion_precession() {
+    let duration = 3.0 * JULIAN_YEAR;
+    let newtonian = perihelion_shift(false, duration);
+    let relativistic = perihelion_shift(true, duration);
+
+    let per_century = (relativistic - newtonian) * 100.0 * JULIAN_YEAR / duration;
+    assert!(
+        (per_century - 42.98).abs() < 1.0,
+        "Expected ~43 arcsec/century of extra precession, got {}",
+        per_century
+    );
+}
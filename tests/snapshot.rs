@@ -0,0 +1,72 @@
+use bevy::{app::Events, prelude::*};
+use bevy_origin_rebasing::*;
+use space::cameras::event::{PitchEvent, YawEvent};
+use space::gravity::{body::*, nbody::NBodyPlugin, system::SystemDefinition};
+use space::look::MouseSettings;
+use space::snapshot::*;
+use space::units::Meters;
+
+fn app() -> AppBuilder {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(OriginRebasingPlugin::new(OriginRebasingSettings {
+            draw_debug_cell: false,
+            ..Default::default()
+        }))
+        .add_plugin(NBodyPlugin)
+        .add_plugin(SnapshotPlugin)
+        .init_resource::<Input<KeyCode>>()
+        .init_resource::<MouseSettings>()
+        .add_event::<YawEvent>()
+        .add_event::<PitchEvent>()
+        .add_startup_system(spawn_system.system());
+    app
+}
+
+fn spawn_system(mut commands: Commands) {
+    SystemDefinition::solar_system().spawn(&mut commands);
+}
+
+fn find(app: &mut AppBuilder, name: &str) -> Option<Entity> {
+    let world = app.world_mut();
+    let mut query = world.query::<(Entity, &Name)>();
+    query
+        .iter(world)
+        .find(|(_, body)| body.as_str() == name)
+        .map(|(entity, _)| entity)
+}
+
+#[test]
+fn respawned_bodies_keep_their_perturbation_components() {
+    let path = std::env::temp_dir().join("space-snapshot-respawn.json");
+    let mut app = app();
+    app.app.update();
+
+    app.world_mut()
+        .get_resource_mut::<Events<SaveSnapshotEvent>>()
+        .unwrap()
+        .send(SaveSnapshotEvent::new(&path));
+    app.app.update();
+
+    let earth = find(&mut app, "earth").unwrap();
+    app.world_mut().despawn(earth);
+    let sun = find(&mut app, "sun").unwrap();
+    app.world_mut().entity_mut(sun).remove::<LightSource>();
+
+    app.world_mut()
+        .get_resource_mut::<Events<LoadSnapshotEvent>>()
+        .unwrap()
+        .send(LoadSnapshotEvent::new(&path));
+    app.app.update();
+    std::fs::remove_file(&path).unwrap();
+
+    let earth = find(&mut app, "earth").expect("The earth should be respawned");
+    let world = app.world_mut();
+    let earth = world.entity(earth);
+    assert!(earth.get::<Radius>().unwrap().0 > Meters(6e6));
+    assert!(earth.get::<ZonalHarmonics>().is_some());
+    assert!(earth.get::<SpinAxis>().is_some());
+    assert!(earth.get::<Atmosphere>().is_some());
+    assert!(earth.get::<GlobalTransform>().is_some());
+    assert!(world.get::<LightSource>(sun).is_some());
+}
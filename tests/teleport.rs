@@ -0,0 +1,44 @@
+use bevy::{math::DVec3, prelude::*};
+use bevy_origin_rebasing::*;
+use space::gravity::{body::*, nbody::NBodyPlugin};
+use space::units::*;
+
+const CELL_SIZE: f64 = 2_000.0;
+
+#[test]
+fn teleporting_a_body_moves_its_position() {
+    let mut app = App::build();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(OriginRebasingPlugin::new(OriginRebasingSettings {
+            cell_size: CELL_SIZE,
+            draw_debug_cell: false,
+            ..Default::default()
+        }))
+        .add_plugin(NBodyPlugin);
+    let body = app
+        .world_mut()
+        .spawn()
+        .insert_bundle(BodyBundle::new(
+            Kilograms(1.0),
+            Position::ZERO,
+            Velocity::ZERO,
+        ))
+        .id();
+    app.app.update();
+
+    let target = DVec3::new(10_500.0, -3_000.0, 0.0);
+    app.world_mut()
+        .get_mut::<SimulationCoordinates>(body)
+        .unwrap()
+        .teleport(target);
+    app.app.update();
+    app.app.update();
+
+    // `sync_transforms` rebuilds the coordinates from `Position`, so the teleport has to stick there
+    let world = app.world_mut();
+    assert_eq!(world.get::<Position>(body).unwrap().0, target);
+    assert_eq!(
+        *world.get::<SimulationCoordinates>(body).unwrap(),
+        SimulationCoordinates::from_translation(target, CELL_SIZE)
+    );
+}